@@ -0,0 +1,43 @@
+use std::path::Path;
+use std::time::Duration;
+
+use anyhow::{Context, Result};
+use hound::WavReader;
+
+/// Loudness and length of a finished recording.
+#[derive(Clone, Copy, Debug)]
+pub struct AudioStats {
+    pub duration: Duration,
+    /// Root mean square of all samples, normalised to `0.0..=1.0`.
+    pub rms: f32,
+}
+
+/// Reads a 16-bit recording as written by `Recorder`.
+pub fn analyze(path: &Path) -> Result<AudioStats> {
+    let mut reader = WavReader::open(path).context("failed to open recording")?;
+    let spec = reader.spec();
+    let mut sum_of_squares = 0f64;
+    let mut count = 0u64;
+    for sample in reader.samples::<i16>() {
+        let sample = sample.context("failed to read recording")? as f64 / i16::MAX as f64;
+        sum_of_squares += sample * sample;
+        count += 1;
+    }
+    let frames = count / u64::from(spec.channels.max(1));
+    Ok(AudioStats {
+        duration: Duration::from_secs_f64(frames as f64 / f64::from(spec.sample_rate)),
+        rms: if count == 0 {
+            0.0
+        } else {
+            (sum_of_squares / count as f64).sqrt() as f32
+        },
+    })
+}
+
+/// Length of a recording, read from the WAV header alone.
+pub fn duration(path: &Path) -> Result<Duration> {
+    let reader = WavReader::open(path).context("failed to open recording")?;
+    Ok(Duration::from_secs_f64(
+        f64::from(reader.duration()) / f64::from(reader.spec().sample_rate),
+    ))
+}
@@ -1 +1,3 @@
+pub mod analysis;
 pub mod recorder;
+pub mod splitter;
@@ -0,0 +1,92 @@
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+use anyhow::{Context, Result};
+use hound::{WavReader, WavWriter};
+
+/// Energy is measured over frames of this length when looking for a pause to cut at.
+const FRAME_MS: u64 = 50;
+
+pub struct AudioChunk {
+    pub path: PathBuf,
+    /// Where the chunk starts in the original recording.
+    pub offset: Duration,
+}
+
+/// Splits a 16-bit recording into chunks of roughly `target` length, cutting at the quietest
+/// frame between `target` and `max` so words are not split in half. Chunk files are written
+/// next to the recording; the caller removes them.
+pub fn split_at_silence(path: &Path, target: Duration, max: Duration) -> Result<Vec<AudioChunk>> {
+    let mut reader = WavReader::open(path).context("failed to open recording")?;
+    let spec = reader.spec();
+    let channels = u64::from(spec.channels.max(1));
+    let samples_per_frame = u64::from(spec.sample_rate) * FRAME_MS / 1000 * channels;
+    let frame_duration = Duration::from_millis(FRAME_MS);
+
+    let mut energies = Vec::new();
+    let mut sum_of_squares = 0f64;
+    let mut in_frame = 0u64;
+    for sample in reader.samples::<i16>() {
+        let sample = f64::from(sample.context("failed to read recording")?);
+        sum_of_squares += sample * sample;
+        in_frame += 1;
+        if in_frame == samples_per_frame {
+            energies.push(sum_of_squares);
+            sum_of_squares = 0.0;
+            in_frame = 0;
+        }
+    }
+    if in_frame > 0 {
+        energies.push(sum_of_squares);
+    }
+
+    let target_frames = (target.as_millis() as u64 / FRAME_MS).max(1) as usize;
+    let max_frames = (max.as_millis() as u64 / FRAME_MS).max(target_frames as u64 + 1) as usize;
+    let mut cuts = Vec::new();
+    let mut start = 0usize;
+    while energies.len() - start > max_frames {
+        let window = start + target_frames..start + max_frames;
+        let cut = window
+            .clone()
+            .min_by(|a, b| energies[*a].total_cmp(&energies[*b]))
+            .unwrap_or(window.end);
+        cuts.push(cut);
+        start = cut;
+    }
+
+    let stem = path
+        .file_stem()
+        .and_then(|stem| stem.to_str())
+        .unwrap_or("recording");
+    let chunk_path = |index: usize| path.with_file_name(format!("{stem}-part{index}.wav"));
+    let mut chunks = vec![AudioChunk {
+        path: chunk_path(0),
+        offset: Duration::ZERO,
+    }];
+    let mut writer =
+        WavWriter::create(&chunks[0].path, spec).context("failed to create chunk file")?;
+    let mut next_cut = cuts
+        .iter()
+        .map(|cut| *cut as u64 * samples_per_frame)
+        .peekable();
+
+    let mut reader = WavReader::open(path).context("failed to open recording")?;
+    for (index, sample) in reader.samples::<i16>().enumerate() {
+        if next_cut.peek() == Some(&(index as u64)) {
+            next_cut.next();
+            writer.finalize().context("failed to finish chunk file")?;
+            let offset = frame_duration * cuts[chunks.len() - 1] as u32;
+            chunks.push(AudioChunk {
+                path: chunk_path(chunks.len()),
+                offset,
+            });
+            writer = WavWriter::create(&chunks[chunks.len() - 1].path, spec)
+                .context("failed to create chunk file")?;
+        }
+        writer
+            .write_sample(sample.context("failed to read recording")?)
+            .context("failed to write chunk file")?;
+    }
+    writer.finalize().context("failed to finish chunk file")?;
+    Ok(chunks)
+}
@@ -1,6 +1,48 @@
+use std::fmt;
 use std::path::Path;
+use std::time::Duration;
 
 use async_trait::async_trait;
+use reqwest::StatusCode;
+use serde::Serialize;
+
+use crate::core::language::LanguageSetting;
+use crate::core::profile::CleanupProfile;
+use crate::core::transcript::Transcript;
+
+/// Error details a provider returned alongside a failed status, parsed from its own format.
+#[derive(Clone, Debug, Default, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ProviderErrorBody {
+    pub code: Option<String>,
+    pub message: Option<String>,
+    pub request_id: Option<String>,
+}
+
+impl ProviderErrorBody {
+    /// Fallback for bodies in an unknown format: keeps a bounded excerpt of the raw text.
+    pub fn from_raw(text: &str) -> Self {
+        let trimmed = text.trim();
+        Self {
+            message: (!trimmed.is_empty()).then(|| trimmed.chars().take(200).collect()),
+            ..Self::default()
+        }
+    }
+}
+
+impl fmt::Display for ProviderErrorBody {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match (&self.code, &self.message) {
+            (Some(code), Some(message)) => write!(f, "{code}: {message}"),
+            (Some(text), None) | (None, Some(text)) => write!(f, "{text}"),
+            (None, None) => write!(f, "no details"),
+        }?;
+        if let Some(request_id) = &self.request_id {
+            write!(f, " (request {request_id})")?;
+        }
+        Ok(())
+    }
+}
 
 #[derive(Debug, thiserror::Error)]
 pub enum AudioProcessorError {
@@ -8,18 +50,162 @@ pub enum AudioProcessorError {
     MissingApiKey,
     #[error("missing gemini api key")]
     MissingGeminiApiKey,
+    #[error("{provider} rejected the api key: {body}")]
+    Unauthorized {
+        provider: &'static str,
+        body: ProviderErrorBody,
+    },
+    #[error("{provider} rate limited the request: {body}")]
+    RateLimited {
+        provider: &'static str,
+        retry_after: Option<Duration>,
+        body: ProviderErrorBody,
+    },
+    #[error("{provider} quota exhausted: {body}")]
+    QuotaExhausted {
+        provider: &'static str,
+        body: ProviderErrorBody,
+    },
+    #[error("{provider} rejected the payload as too large: {body}")]
+    PayloadTooLarge {
+        provider: &'static str,
+        body: ProviderErrorBody,
+    },
+    #[error("{provider} request timed out")]
+    Timeout { provider: &'static str },
+    #[error("could not reach {provider}: {message}")]
+    NetworkUnreachable {
+        provider: &'static str,
+        message: String,
+    },
+    #[error("{provider} returned server error {status}: {body}")]
+    ProviderServerError {
+        provider: &'static str,
+        status: u16,
+        body: ProviderErrorBody,
+    },
     #[error("audio processing request failed: {0}")]
     Request(String),
     #[error("transcription response was empty")]
     EmptyTranscript,
+    #[error("monthly spending limit of ${limit:.2} has been reached (${spent:.2} this month)")]
+    SpendingLimitReached { limit: f64, spent: f64 },
+}
+
+impl AudioProcessorError {
+    /// Classifies a failure to get any response at all.
+    pub fn from_transport(provider: &'static str, err: &reqwest::Error) -> Self {
+        if err.is_timeout() {
+            AudioProcessorError::Timeout { provider }
+        } else {
+            AudioProcessorError::NetworkUnreachable {
+                provider,
+                message: err.to_string(),
+            }
+        }
+    }
+
+    /// Classifies a non-success status together with the provider's parsed error body.
+    pub fn from_status(
+        provider: &'static str,
+        status: StatusCode,
+        retry_after: Option<Duration>,
+        body: ProviderErrorBody,
+    ) -> Self {
+        let mentions_quota = body
+            .message
+            .as_deref()
+            .is_some_and(|message| message.to_lowercase().contains("quota"));
+        match status {
+            StatusCode::UNAUTHORIZED | StatusCode::FORBIDDEN => {
+                AudioProcessorError::Unauthorized { provider, body }
+            }
+            StatusCode::PAYMENT_REQUIRED => AudioProcessorError::QuotaExhausted { provider, body },
+            StatusCode::TOO_MANY_REQUESTS if mentions_quota => {
+                AudioProcessorError::QuotaExhausted { provider, body }
+            }
+            StatusCode::TOO_MANY_REQUESTS => AudioProcessorError::RateLimited {
+                provider,
+                retry_after,
+                body,
+            },
+            StatusCode::PAYLOAD_TOO_LARGE => {
+                AudioProcessorError::PayloadTooLarge { provider, body }
+            }
+            status if status.is_server_error() => AudioProcessorError::ProviderServerError {
+                provider,
+                status: status.as_u16(),
+                body,
+            },
+            status => {
+                AudioProcessorError::Request(format!("{provider} returned status {status}: {body}"))
+            }
+        }
+    }
+
+    /// Stable identifier the UI can switch on.
+    pub fn kind(&self) -> &'static str {
+        match self {
+            AudioProcessorError::MissingApiKey | AudioProcessorError::MissingGeminiApiKey => {
+                "missing_api_key"
+            }
+            AudioProcessorError::Unauthorized { .. } => "unauthorized",
+            AudioProcessorError::RateLimited { .. } => "rate_limited",
+            AudioProcessorError::QuotaExhausted { .. } => "quota_exhausted",
+            AudioProcessorError::PayloadTooLarge { .. } => "payload_too_large",
+            AudioProcessorError::Timeout { .. } => "timeout",
+            AudioProcessorError::NetworkUnreachable { .. } => "network_unreachable",
+            AudioProcessorError::ProviderServerError { .. } => "provider_server_error",
+            AudioProcessorError::Request(_) => "request",
+            AudioProcessorError::EmptyTranscript => "empty_transcript",
+            AudioProcessorError::SpendingLimitReached { .. } => "spending_limit_reached",
+        }
+    }
+
+    pub fn provider(&self) -> Option<&'static str> {
+        match self {
+            AudioProcessorError::MissingApiKey => Some("deepgram"),
+            AudioProcessorError::MissingGeminiApiKey => Some("gemini"),
+            AudioProcessorError::Unauthorized { provider, .. }
+            | AudioProcessorError::RateLimited { provider, .. }
+            | AudioProcessorError::QuotaExhausted { provider, .. }
+            | AudioProcessorError::PayloadTooLarge { provider, .. }
+            | AudioProcessorError::Timeout { provider }
+            | AudioProcessorError::NetworkUnreachable { provider, .. }
+            | AudioProcessorError::ProviderServerError { provider, .. } => Some(provider),
+            AudioProcessorError::Request(_)
+            | AudioProcessorError::EmptyTranscript
+            | AudioProcessorError::SpendingLimitReached { .. } => None,
+        }
+    }
+
+    /// Whether the next provider in a fallback chain should be tried.
+    pub fn should_fall_back(&self) -> bool {
+        matches!(
+            self,
+            AudioProcessorError::RateLimited { .. }
+                | AudioProcessorError::QuotaExhausted { .. }
+                | AudioProcessorError::Timeout { .. }
+                | AudioProcessorError::NetworkUnreachable { .. }
+                | AudioProcessorError::ProviderServerError { .. }
+        )
+    }
 }
 
 #[async_trait]
 pub trait AudioProcessor: Send + Sync {
-    async fn process_file(&self, audio_path: &Path) -> Result<String, AudioProcessorError>;
+    async fn process_file(
+        &self,
+        audio_path: &Path,
+        language: &LanguageSetting,
+    ) -> Result<Transcript, AudioProcessorError>;
 }
 
 #[async_trait]
 pub trait TranscriptCleaner: Send + Sync {
-    async fn clean(&self, transcript: &str) -> Result<String, AudioProcessorError>;
+    async fn clean(
+        &self,
+        transcript: &Transcript,
+        profile: &CleanupProfile,
+    ) -> Result<String, AudioProcessorError>;
 }
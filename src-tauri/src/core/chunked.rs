@@ -0,0 +1,110 @@
+use std::path::Path;
+use std::sync::Arc;
+use std::time::Duration;
+
+use tokio::sync::Semaphore;
+use tokio::task::JoinSet;
+
+use crate::audio::splitter::AudioChunk;
+use crate::core::audio_processor::{AudioProcessor, AudioProcessorError};
+use crate::core::fallback::FallbackTranscriber;
+use crate::core::language::LanguageSetting;
+use crate::core::transcript::Transcript;
+
+/// Recordings longer than `CHUNK_MAX` are split into chunks of about `CHUNK_TARGET`.
+pub const CHUNK_TARGET: Duration = Duration::from_secs(60);
+pub const CHUNK_MAX: Duration = Duration::from_secs(90);
+const MAX_PARALLEL_CHUNKS: usize = 4;
+const CHUNK_ATTEMPTS: u32 = 3;
+const CHUNK_RETRY_DELAY: Duration = Duration::from_secs(1);
+
+/// Transcribes chunks through the fallback chain, a few at a time, and joins the results in
+/// recording order. A chunk that fails is retried on its own before the whole dictation fails.
+pub async fn transcribe_chunks(
+    fallback: Arc<FallbackTranscriber>,
+    chain: Arc<Vec<(String, Arc<dyn AudioProcessor>)>>,
+    chunks: &[AudioChunk],
+    language: &LanguageSetting,
+) -> Result<Transcript, AudioProcessorError> {
+    let semaphore = Arc::new(Semaphore::new(MAX_PARALLEL_CHUNKS));
+    let mut tasks = JoinSet::new();
+    for (index, chunk) in chunks.iter().enumerate() {
+        let fallback = fallback.clone();
+        let chain = chain.clone();
+        let semaphore = semaphore.clone();
+        let path = chunk.path.clone();
+        let language = language.clone();
+        tasks.spawn(async move {
+            let _permit = semaphore.acquire_owned().await;
+            (
+                index,
+                transcribe_chunk(&fallback, &chain, &path, &language, index).await,
+            )
+        });
+    }
+
+    let mut results: Vec<Option<Transcript>> = vec![None; chunks.len()];
+    while let Some(joined) = tasks.join_next().await {
+        let (index, result) = joined
+            .map_err(|err| AudioProcessorError::Request(format!("chunk task failed: {err}")))?;
+        match result {
+            Ok(transcript) => results[index] = Some(transcript),
+            // A chunk of pure silence is fine as long as some other chunk has speech.
+            Err(AudioProcessorError::EmptyTranscript) => {}
+            Err(err) => {
+                tasks.abort_all();
+                return Err(err);
+            }
+        }
+    }
+    stitch(results, chunks)
+}
+
+async fn transcribe_chunk(
+    fallback: &FallbackTranscriber,
+    chain: &[(String, Arc<dyn AudioProcessor>)],
+    path: &Path,
+    language: &LanguageSetting,
+    index: usize,
+) -> Result<Transcript, AudioProcessorError> {
+    let mut attempt = 1;
+    loop {
+        match fallback.transcribe(chain, path, language).await {
+            Err(err) if err.should_fall_back() && attempt < CHUNK_ATTEMPTS => {
+                log::warn!("chunk {index} failed on attempt {attempt}; retrying: {err}");
+                tokio::time::sleep(CHUNK_RETRY_DELAY * attempt).await;
+                attempt += 1;
+            }
+            result => return result,
+        }
+    }
+}
+
+fn stitch(
+    results: Vec<Option<Transcript>>,
+    chunks: &[AudioChunk],
+) -> Result<Transcript, AudioProcessorError> {
+    let mut parts = results
+        .into_iter()
+        .zip(chunks)
+        .filter_map(|(transcript, chunk)| transcript.map(|t| (t, chunk.offset.as_secs_f64())));
+    let (mut stitched, offset) = parts.next().ok_or(AudioProcessorError::EmptyTranscript)?;
+    shift_words(&mut stitched, offset);
+    // Runner-ups describe a single chunk, not the whole dictation.
+    stitched.alternatives.clear();
+    for (mut transcript, offset) in parts {
+        shift_words(&mut transcript, offset);
+        stitched.text.push(' ');
+        stitched.text.push_str(transcript.text.trim());
+        stitched.words.append(&mut transcript.words);
+        stitched.language = stitched.language.or(transcript.language);
+    }
+    Ok(stitched)
+}
+
+fn shift_words(transcript: &mut Transcript, offset: f64) {
+    for word in &mut transcript.words {
+        word.start += offset;
+        word.end += offset;
+    }
+}
@@ -0,0 +1,55 @@
+use std::collections::{HashMap, VecDeque};
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+/// Skips a provider once it has failed `failure_threshold` times within `window`. The
+/// breaker closes again on its own as those failures age out of the window.
+pub struct CircuitBreaker {
+    failure_threshold: usize,
+    window: Duration,
+    failures: Mutex<HashMap<String, VecDeque<Instant>>>,
+}
+
+impl CircuitBreaker {
+    pub fn new(failure_threshold: usize, window: Duration) -> Self {
+        Self {
+            failure_threshold,
+            window,
+            failures: Mutex::new(HashMap::new()),
+        }
+    }
+
+    pub fn is_open(&self, provider_id: &str) -> bool {
+        let Ok(mut failures) = self.failures.lock() else {
+            return false;
+        };
+        let Some(recent) = failures.get_mut(provider_id) else {
+            return false;
+        };
+        Self::prune(recent, self.window);
+        recent.len() >= self.failure_threshold
+    }
+
+    pub fn record_failure(&self, provider_id: &str) {
+        if let Ok(mut failures) = self.failures.lock() {
+            let recent = failures.entry(provider_id.to_string()).or_default();
+            Self::prune(recent, self.window);
+            recent.push_back(Instant::now());
+        }
+    }
+
+    pub fn record_success(&self, provider_id: &str) {
+        if let Ok(mut failures) = self.failures.lock() {
+            failures.remove(provider_id);
+        }
+    }
+
+    fn prune(recent: &mut VecDeque<Instant>, window: Duration) {
+        while recent
+            .front()
+            .is_some_and(|failed_at| failed_at.elapsed() > window)
+        {
+            recent.pop_front();
+        }
+    }
+}
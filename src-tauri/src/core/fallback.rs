@@ -0,0 +1,75 @@
+use std::path::Path;
+use std::sync::Arc;
+use std::time::Duration;
+
+use crate::core::audio_processor::{AudioProcessor, AudioProcessorError};
+use crate::core::circuit_breaker::CircuitBreaker;
+use crate::core::language::LanguageSetting;
+use crate::core::transcript::Transcript;
+
+const BREAKER_FAILURE_THRESHOLD: usize = 3;
+const BREAKER_WINDOW: Duration = Duration::from_secs(5 * 60);
+
+/// Runs a transcription through an ordered chain of providers, moving on to the next one when
+/// a provider is unavailable and skipping providers whose circuit breaker is open.
+pub struct FallbackTranscriber {
+    breaker: CircuitBreaker,
+}
+
+impl FallbackTranscriber {
+    pub fn new() -> Self {
+        Self {
+            breaker: CircuitBreaker::new(BREAKER_FAILURE_THRESHOLD, BREAKER_WINDOW),
+        }
+    }
+
+    pub async fn transcribe(
+        &self,
+        chain: &[(String, Arc<dyn AudioProcessor>)],
+        audio_path: &Path,
+        language: &LanguageSetting,
+    ) -> Result<Transcript, AudioProcessorError> {
+        let mut candidates: Vec<_> = chain
+            .iter()
+            .filter(|(id, _)| {
+                let is_open = self.breaker.is_open(id);
+                if is_open {
+                    log::warn!("skipping provider={id}; circuit breaker open");
+                }
+                !is_open
+            })
+            .collect();
+        if candidates.is_empty() {
+            // Every provider has been failing; trying them anyway beats failing outright.
+            candidates = chain.iter().collect();
+        }
+
+        let mut last_error = None;
+        for (id, processor) in candidates {
+            match processor.process_file(audio_path, language).await {
+                Ok(transcript) => {
+                    self.breaker.record_success(id);
+                    log::info!(
+                        "transcription provided by provider={id} language={} confidence={}",
+                        transcript.language.as_deref().unwrap_or("unknown"),
+                        transcript
+                            .average_confidence()
+                            .map(|confidence| format!("{confidence:.2}"))
+                            .unwrap_or_else(|| "n/a".to_string())
+                    );
+                    return Ok(transcript);
+                }
+                Err(err) if err.should_fall_back() => {
+                    self.breaker.record_failure(id);
+                    log::warn!("provider={id} unavailable, trying next fallback: {err}");
+                    last_error = Some(err);
+                }
+                Err(err) => return Err(err),
+            }
+        }
+
+        Err(last_error.unwrap_or_else(|| {
+            AudioProcessorError::Request("no transcription provider configured".to_string())
+        }))
+    }
+}
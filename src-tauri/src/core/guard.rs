@@ -0,0 +1,85 @@
+use thiserror::Error;
+
+use crate::audio::analysis::AudioStats;
+use crate::core::transcript::Transcript;
+
+/// Phrases speech models are known to invent for silence or background noise.
+pub const DEFAULT_HALLUCINATION_PHRASES: &[&str] = &[
+    "thank you",
+    "thank you very much",
+    "thanks for watching",
+    "thank you for watching",
+    "please subscribe",
+    "like and subscribe",
+    "bye",
+    "you",
+    "subtitles by the amara.org community",
+];
+
+/// Below this RMS the recording is treated as silence whatever the transcript says.
+const SILENCE_RMS: f32 = 0.003;
+/// Blocklisted phrases pass only when the provider is at least this sure of them.
+const BLOCKLIST_MIN_CONFIDENCE: f64 = 0.9;
+const MIN_AVERAGE_CONFIDENCE: f64 = 0.4;
+/// Fast speech is around four words per second; well beyond that the text was not spoken.
+const MAX_WORDS_PER_SECOND: f64 = 7.0;
+
+#[derive(Debug, Error)]
+pub enum GuardRejection {
+    #[error("recording is near silent (rms {rms:.4})")]
+    Silence { rms: f32 },
+    #[error("transcript matches blocklisted phrase \"{phrase}\"")]
+    BlocklistedPhrase { phrase: String },
+    #[error("average word confidence {confidence:.2} is too low")]
+    LowConfidence { confidence: f64 },
+    #[error("{words_per_second:.1} words per second is more than the audio can hold")]
+    TooManyWords { words_per_second: f64 },
+}
+
+/// Rejects transcripts the audio cannot plausibly support.
+pub fn check(
+    transcript: &Transcript,
+    stats: &AudioStats,
+    blocklist: &[String],
+) -> Result<(), GuardRejection> {
+    if stats.rms < SILENCE_RMS {
+        return Err(GuardRejection::Silence { rms: stats.rms });
+    }
+
+    let confidence = transcript.average_confidence();
+    let normalized = normalize(&transcript.text);
+    if let Some(phrase) = blocklist
+        .iter()
+        .find(|phrase| normalize(phrase) == normalized)
+    {
+        if confidence.is_none_or(|confidence| confidence < BLOCKLIST_MIN_CONFIDENCE) {
+            return Err(GuardRejection::BlocklistedPhrase {
+                phrase: phrase.clone(),
+            });
+        }
+    }
+
+    if let Some(confidence) = confidence.filter(|c| *c < MIN_AVERAGE_CONFIDENCE) {
+        return Err(GuardRejection::LowConfidence { confidence });
+    }
+
+    let seconds = stats.duration.as_secs_f64();
+    let word_count = transcript.text.split_whitespace().count();
+    if seconds > 0.0 && word_count as f64 / seconds > MAX_WORDS_PER_SECOND {
+        return Err(GuardRejection::TooManyWords {
+            words_per_second: word_count as f64 / seconds,
+        });
+    }
+    Ok(())
+}
+
+fn normalize(text: &str) -> String {
+    text.split_whitespace()
+        .map(|word| {
+            word.trim_matches(|c: char| !c.is_alphanumeric())
+                .to_lowercase()
+        })
+        .filter(|word| !word.is_empty())
+        .collect::<Vec<_>>()
+        .join(" ")
+}
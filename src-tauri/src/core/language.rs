@@ -0,0 +1,73 @@
+use serde::{Deserialize, Serialize};
+
+/// Languages offered in the tray and settings, as `(setting value, label)`. Forced languages
+/// are BCP-47 codes understood by both Deepgram and Whisper.
+pub const LANGUAGE_OPTIONS: &[(&str, &str)] = &[
+    ("auto", "Auto-detect"),
+    ("multi", "Multilingual"),
+    ("en", "English"),
+    ("de", "German"),
+    ("es", "Spanish"),
+    ("fr", "French"),
+    ("it", "Italian"),
+    ("nl", "Dutch"),
+    ("pt", "Portuguese"),
+];
+
+/// The English name of a language code from `LANGUAGE_OPTIONS`, or the code itself.
+pub fn label(code: &str) -> &str {
+    LANGUAGE_OPTIONS
+        .iter()
+        .find(|(value, _)| *value == code)
+        .map_or(code, |(_, label)| *label)
+}
+
+/// Which language the transcription provider should listen for.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(from = "String", into = "String")]
+pub enum LanguageSetting {
+    /// Detect a single language per recording.
+    Auto,
+    /// Expect several languages, possibly within one sentence.
+    Multi,
+    Forced(String),
+}
+
+impl Default for LanguageSetting {
+    fn default() -> Self {
+        LanguageSetting::Forced("en".to_string())
+    }
+}
+
+impl LanguageSetting {
+    pub fn as_str(&self) -> &str {
+        match self {
+            LanguageSetting::Auto => "auto",
+            LanguageSetting::Multi => "multi",
+            LanguageSetting::Forced(code) => code,
+        }
+    }
+
+    pub fn forced_code(&self) -> Option<&str> {
+        match self {
+            LanguageSetting::Forced(code) => Some(code),
+            _ => None,
+        }
+    }
+}
+
+impl From<String> for LanguageSetting {
+    fn from(value: String) -> Self {
+        match value.as_str() {
+            "auto" => LanguageSetting::Auto,
+            "multi" => LanguageSetting::Multi,
+            _ => LanguageSetting::Forced(value),
+        }
+    }
+}
+
+impl From<LanguageSetting> for String {
+    fn from(value: LanguageSetting) -> Self {
+        value.as_str().to_string()
+    }
+}
@@ -0,0 +1,243 @@
+use serde::{Deserialize, Serialize};
+
+use crate::core::transcript::{Transcript, TranscriptWord};
+
+/// Confidence given to "no word here" when only one engine heard something, as in ROVER.
+const NULL_CONFIDENCE: f64 = 0.5;
+/// Stand-in for engines that report no per-word confidence.
+const UNKNOWN_CONFIDENCE: f64 = 0.5;
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum MergeStrategy {
+    /// Keep whichever transcript has the higher average word confidence.
+    #[default]
+    HigherConfidence,
+    /// Align both transcripts and vote word by word.
+    WordByWord,
+}
+
+/// Runs a second transcription provider next to the active one. `provider: None` turns the
+/// mode off.
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SecondOpinionSettings {
+    pub provider: Option<String>,
+    #[serde(default)]
+    pub strategy: MergeStrategy,
+}
+
+/// One aligned position where the engines heard different things.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Disagreement {
+    pub primary: Option<String>,
+    pub secondary: Option<String>,
+    pub chosen: Option<String>,
+    /// Seconds into the recording.
+    pub start: f64,
+}
+
+pub struct MergeOutcome {
+    pub transcript: Transcript,
+    pub disagreements: Vec<Disagreement>,
+}
+
+pub fn merge(primary: Transcript, secondary: Transcript, strategy: MergeStrategy) -> MergeOutcome {
+    // Providers without word timings (e.g. a whisper server) leave nothing to align or vote
+    // on; every word would look like a one-sided insertion.
+    if primary.words.is_empty() || secondary.words.is_empty() {
+        log::info!(
+            "second opinion from {} not merged: word timings missing",
+            secondary.provider
+        );
+        return MergeOutcome {
+            transcript: primary,
+            disagreements: Vec::new(),
+        };
+    }
+    let pairs = align(&primary.words, &secondary.words);
+    match strategy {
+        MergeStrategy::HigherConfidence => {
+            let secondary_wins = secondary.average_confidence().unwrap_or(UNKNOWN_CONFIDENCE)
+                > primary.average_confidence().unwrap_or(UNKNOWN_CONFIDENCE);
+            let disagreements = pairs
+                .iter()
+                .filter(|(p, s)| !same_word(*p, *s))
+                .map(|(p, s)| Disagreement {
+                    primary: p.map(|word| word.text.clone()),
+                    secondary: s.map(|word| word.text.clone()),
+                    chosen: if secondary_wins { s } else { p }.map(|word| word.text.clone()),
+                    start: p.or(*s).map_or(0.0, |word| word.start),
+                })
+                .collect();
+            MergeOutcome {
+                transcript: if secondary_wins { secondary } else { primary },
+                disagreements,
+            }
+        }
+        MergeStrategy::WordByWord => {
+            let mut words = Vec::with_capacity(pairs.len());
+            let mut disagreements = Vec::new();
+            for (p, s) in &pairs {
+                if same_word(*p, *s) {
+                    words.extend(p.cloned());
+                    continue;
+                }
+                let chosen = match (*p, *s) {
+                    (Some(p), Some(s)) => Some(if confidence(s) > confidence(p) { s } else { p }),
+                    (Some(only), None) | (None, Some(only)) => {
+                        Some(only).filter(|word| confidence(word) > NULL_CONFIDENCE)
+                    }
+                    (None, None) => None,
+                };
+                disagreements.push(Disagreement {
+                    primary: p.map(|word| word.text.clone()),
+                    secondary: s.map(|word| word.text.clone()),
+                    chosen: chosen.map(|word| word.text.clone()),
+                    start: p.or(*s).map_or(0.0, |word| word.start),
+                });
+                words.extend(chosen.cloned());
+            }
+            // Without disagreements the primary's own formatting is the better text.
+            if disagreements.is_empty() {
+                return MergeOutcome {
+                    transcript: primary,
+                    disagreements,
+                };
+            }
+            let text = words
+                .iter()
+                .map(|word| word.text.as_str())
+                .collect::<Vec<_>>()
+                .join(" ");
+            MergeOutcome {
+                transcript: Transcript {
+                    text,
+                    words,
+                    alternatives: vec![primary.text, secondary.text],
+                    provider: format!("{}+{}", primary.provider, secondary.provider),
+                    ..primary
+                },
+                disagreements,
+            }
+        }
+    }
+}
+
+fn confidence(word: &TranscriptWord) -> f64 {
+    word.confidence.unwrap_or(UNKNOWN_CONFIDENCE)
+}
+
+fn normalize(text: &str) -> String {
+    text.chars()
+        .filter(|c| c.is_alphanumeric())
+        .flat_map(char::to_lowercase)
+        .collect()
+}
+
+fn same_word(primary: Option<&TranscriptWord>, secondary: Option<&TranscriptWord>) -> bool {
+    match (primary, secondary) {
+        (Some(p), Some(s)) => normalize(&p.text) == normalize(&s.text),
+        _ => false,
+    }
+}
+
+/// Minimum edit distance alignment over normalised words. Each pair holds a word from either
+/// side, or both when they were matched or substituted.
+fn align<'a>(
+    primary: &'a [TranscriptWord],
+    secondary: &'a [TranscriptWord],
+) -> Vec<(Option<&'a TranscriptWord>, Option<&'a TranscriptWord>)> {
+    let p: Vec<String> = primary.iter().map(|word| normalize(&word.text)).collect();
+    let s: Vec<String> = secondary.iter().map(|word| normalize(&word.text)).collect();
+    let (n, m) = (p.len(), s.len());
+    let mut cost = vec![vec![0usize; m + 1]; n + 1];
+    for (i, row) in cost.iter_mut().enumerate() {
+        row[0] = i;
+    }
+    for (j, cell) in cost[0].iter_mut().enumerate() {
+        *cell = j;
+    }
+    for i in 1..=n {
+        for j in 1..=m {
+            let substitution = cost[i - 1][j - 1] + usize::from(p[i - 1] != s[j - 1]);
+            cost[i][j] = substitution.min(cost[i - 1][j] + 1).min(cost[i][j - 1] + 1);
+        }
+    }
+
+    let mut pairs = Vec::with_capacity(n.max(m));
+    let (mut i, mut j) = (n, m);
+    while i > 0 || j > 0 {
+        if i > 0 && j > 0 && cost[i][j] == cost[i - 1][j - 1] + usize::from(p[i - 1] != s[j - 1]) {
+            pairs.push((Some(&primary[i - 1]), Some(&secondary[j - 1])));
+            i -= 1;
+            j -= 1;
+        } else if i > 0 && cost[i][j] == cost[i - 1][j] + 1 {
+            pairs.push((Some(&primary[i - 1]), None));
+            i -= 1;
+        } else {
+            pairs.push((None, Some(&secondary[j - 1])));
+            j -= 1;
+        }
+    }
+    pairs.reverse();
+    pairs
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn transcript(provider: &str, text: &str, confidence: Option<f64>) -> Transcript {
+        let words = match confidence {
+            Some(confidence) => text
+                .split_whitespace()
+                .enumerate()
+                .map(|(index, word)| TranscriptWord {
+                    text: word.to_string(),
+                    start: index as f64,
+                    end: index as f64 + 0.5,
+                    confidence: Some(confidence),
+                    alternatives: Vec::new(),
+                })
+                .collect(),
+            None => Vec::new(),
+        };
+        Transcript {
+            text: text.to_string(),
+            words,
+            alternatives: Vec::new(),
+            language: None,
+            provider: provider.to_string(),
+            request_id: None,
+            context: None,
+        }
+    }
+
+    #[test]
+    fn keeps_the_primary_when_a_side_has_no_word_timings() {
+        for strategy in [MergeStrategy::WordByWord, MergeStrategy::HigherConfidence] {
+            let primary = transcript("deepgram", "ship it on friday", Some(0.4));
+            let secondary = transcript("whisper_server", "ship it on Friday.", None);
+            let outcome = merge(primary, secondary, strategy);
+            assert_eq!(outcome.transcript.text, "ship it on friday");
+            assert!(outcome.disagreements.is_empty());
+
+            let primary = transcript("whisper_server", "ship it on Friday.", None);
+            let secondary = transcript("deepgram", "ship it on friday", Some(0.9));
+            let outcome = merge(primary, secondary, strategy);
+            assert_eq!(outcome.transcript.text, "ship it on Friday.");
+            assert!(outcome.disagreements.is_empty());
+        }
+    }
+
+    #[test]
+    fn votes_word_by_word_when_both_sides_have_timings() {
+        let primary = transcript("deepgram", "ship it on friday", Some(0.4));
+        let secondary = transcript("other", "ship it on monday", Some(0.9));
+        let outcome = merge(primary, secondary, MergeStrategy::WordByWord);
+        assert_eq!(outcome.transcript.text, "ship it on monday");
+        assert_eq!(outcome.disagreements.len(), 1);
+    }
+}
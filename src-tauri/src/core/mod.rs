@@ -1,2 +1,12 @@
 pub mod audio_processor;
+pub mod chunked;
+pub mod circuit_breaker;
+pub mod fallback;
+pub mod guard;
+pub mod language;
+pub mod merge;
 pub mod orchestrator;
+pub mod pipeline;
+pub mod profile;
+pub mod review;
+pub mod transcript;
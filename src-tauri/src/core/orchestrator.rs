@@ -1,29 +1,64 @@
-use std::path::PathBuf;
-use std::sync::atomic::{AtomicU32, Ordering};
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, AtomicU32, Ordering};
 use std::sync::Arc;
 use std::time::{Duration, Instant};
 
-use anyhow::{Context, Result};
+use anyhow::{bail, Context, Result};
 use chrono::Utc;
 use serde::Serialize;
-use tauri::{AppHandle, Emitter, Manager, PhysicalPosition};
+use tauri::{AppHandle, Emitter, LogicalSize, Manager, PhysicalPosition};
 use tokio::sync::Mutex;
 use tokio::task::JoinHandle;
 use tracing::instrument;
 
+use crate::audio::analysis::{self, AudioStats};
 use crate::audio::recorder::Recorder;
-use crate::core::audio_processor::{AudioProcessor, AudioProcessorError, TranscriptCleaner};
-use crate::injection::clipboard_injector::ClipboardInjector;
+use crate::audio::splitter;
+use crate::core::audio_processor::{AudioProcessor, AudioProcessorError};
+use crate::core::chunked::{self, CHUNK_MAX, CHUNK_TARGET};
+use crate::core::fallback::FallbackTranscriber;
+use crate::core::guard;
+use crate::core::language::LanguageSetting;
+use crate::core::merge::{merge, MergeStrategy};
+use crate::core::pipeline::{CleanupPipeline, StageKind};
+use crate::core::profile::{self, CleanupProfile};
+use crate::core::review::{
+    ReviewDecision, ReviewGate, ReviewRequestPayload, LOW_CONFIDENCE_THRESHOLD, REVIEW_TIMEOUT,
+};
+use crate::core::transcript::Transcript;
+use crate::focus::caret_context;
+use crate::focus::foreground::{self, ForegroundApp};
+use crate::focus::rules::{self, AppRule};
+use crate::history::store::{HistoryEntry, HistoryStore, SecondOpinionRecord};
+use crate::injection::clipboard_injector::{self, ClipboardInjector, InjectionMethod};
+use crate::processors::cleanup_prompt;
+use crate::processors::registry::ProviderRegistry;
 use crate::settings::stronghold_store::SecureKeyStore;
+use crate::usage::quota::SpendingLimit;
+use crate::usage::store::UsageStore;
 
 const TRAY_ID: &str = "air_keys_tray";
+const SETTINGS_WINDOW_ID: &str = "settings";
 const RECORDING_WINDOW_ID: &str = "recording";
 const RECORDING_AMPLITUDE_EVENT: &str = "recording-amplitude";
 const RECORDING_STATE_EVENT: &str = "recording-state";
+const PROVIDER_ERROR_EVENT: &str = "provider-error";
+const REVIEW_REQUESTED_EVENT: &str = "review-requested";
+const ALTERNATIVES_EVENT: &str = "alternatives";
 const AMPLITUDE_POLL_MS: u64 = 50;
 /// Offset from bottom of screen (above taskbar/toolbar) in logical pixels.
 const RECORDING_BOTTOM_OFFSET: i32 = 72;
 const MIN_RECORDING_DURATION: Duration = Duration::from_millis(500);
+/// Matches the recording window size in tauri.conf.json.
+const RECORDING_WINDOW_SIZE: (f64, f64) = (220.0, 84.0);
+const REVIEW_WINDOW_SIZE: (f64, f64) = (560.0, 220.0);
+const ALTERNATIVES_WINDOW_SIZE: (f64, f64) = (420.0, 180.0);
+/// The picked alternative is pasted once the hotkey has been idle this long.
+const ALTERNATIVE_COMMIT_DELAY: Duration = Duration::from_millis(1200);
+/// Time for the previously focused app to regain focus once the review overlay hides.
+const REVIEW_FOCUS_RESTORE_MS: u64 = 150;
+/// Used for command mode when the cleanup pipeline has no post-processing stage timeout.
+const TRANSFORM_TIMEOUT: Duration = Duration::from_secs(30);
 
 #[derive(Clone, Serialize)]
 struct RecordingAmplitudePayload {
@@ -35,37 +70,110 @@ struct RecordingStatePayload<'a> {
     state: &'a str,
 }
 
+#[derive(Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct ProviderErrorPayload {
+    kind: &'static str,
+    provider: Option<&'static str>,
+    message: String,
+}
+
+#[derive(Clone, Serialize)]
+struct AlternativesPayload<'a> {
+    candidates: &'a [String],
+    selected: usize,
+}
+
+/// What the last dictation pasted and what it could be swapped for.
+struct PastedCandidates {
+    candidates: Vec<String>,
+    pasted: usize,
+    selected: usize,
+    seq: u64,
+    method: InjectionMethod,
+}
+
+impl PastedCandidates {
+    /// Multi-line pastes cannot be reselected reliably, so they offer nothing to swap.
+    fn pickable(&self) -> bool {
+        self.candidates.len() > 1
+            && !clipboard_injector::is_multiline(&self.candidates[self.pasted])
+    }
+}
+
+/// Deletes the recording once transcription is done with it, on every return path.
+struct RecordingFile<'a>(&'a Path);
+
+impl Drop for RecordingFile<'_> {
+    fn drop(&mut self) {
+        let _ = std::fs::remove_file(self.0);
+    }
+}
+
 pub struct DictationOrchestrator {
     app_handle: AppHandle,
     recorder: Mutex<Recorder>,
-    processor: Arc<dyn AudioProcessor>,
-    cleaner: Arc<dyn TranscriptCleaner>,
+    registry: Arc<ProviderRegistry>,
+    fallback: Arc<FallbackTranscriber>,
+    review_gate: ReviewGate,
+    cleanup: CleanupPipeline,
     key_store: Arc<dyn SecureKeyStore>,
+    history: Arc<HistoryStore>,
+    spending: Arc<SpendingLimit>,
     injector: ClipboardInjector,
     recording_path: Mutex<Option<PathBuf>>,
     recording_started_at: Mutex<Option<Instant>>,
+    /// The rule for the application focused when the current recording started.
+    recording_rule: Mutex<Option<AppRule>>,
+    last_foreground_app: Mutex<Option<ForegroundApp>>,
     amplitude_level: Arc<AtomicU32>,
     level_emitter_task: Mutex<Option<JoinHandle<()>>>,
+    pasted_candidates: Mutex<Option<PastedCandidates>>,
+    /// Whether `pasted_candidates` holds something to swap in, readable without the lock so the
+    /// keyboard hook can leave Alt+` to the focused app otherwise.
+    alternatives_available: AtomicBool,
+    /// Text copied when a command recording started; its presence makes the recording a
+    /// command to apply to that text rather than a dictation.
+    command_selection: Mutex<Option<String>>,
+    /// Text before the caret when the current recording started, if context capture is on.
+    recording_context: Mutex<Option<String>>,
 }
 
 impl DictationOrchestrator {
     pub fn new(
         app_handle: AppHandle,
-        processor: Arc<dyn AudioProcessor>,
-        cleaner: Arc<dyn TranscriptCleaner>,
+        registry: Arc<ProviderRegistry>,
         key_store: Arc<dyn SecureKeyStore>,
+        history: Arc<HistoryStore>,
+        usage: Arc<UsageStore>,
     ) -> Result<Self> {
+        let spending = Arc::new(SpendingLimit::new(
+            app_handle.clone(),
+            key_store.clone(),
+            usage,
+        ));
+        let cleanup = CleanupPipeline::new(key_store.clone(), registry.clone(), spending.clone());
         Ok(Self {
             app_handle,
             recorder: Mutex::new(Recorder::new()?),
-            processor,
-            cleaner,
+            registry,
+            fallback: Arc::new(FallbackTranscriber::new()),
+            review_gate: ReviewGate::new(),
+            cleanup,
             key_store,
+            history,
+            spending,
             injector: ClipboardInjector::new(),
             recording_path: Mutex::new(None),
             recording_started_at: Mutex::new(None),
+            recording_rule: Mutex::new(None),
+            last_foreground_app: Mutex::new(None),
             amplitude_level: Arc::new(AtomicU32::new(0.0f32.to_bits())),
             level_emitter_task: Mutex::new(None),
+            pasted_candidates: Mutex::new(None),
+            alternatives_available: AtomicBool::new(false),
+            command_selection: Mutex::new(None),
+            recording_context: Mutex::new(None),
         })
     }
 
@@ -80,6 +188,9 @@ impl DictationOrchestrator {
         self.stop_level_emitter().await;
         let maybe_path = self.recording_path.lock().await.take();
         let _started_at = self.recording_started_at.lock().await.take();
+        let _rule = self.recording_rule.lock().await.take();
+        let _selection = self.command_selection.lock().await.take();
+        let _context = self.recording_context.lock().await.take();
         drop(recorder);
 
         if let Some(path) = maybe_path {
@@ -99,6 +210,9 @@ impl DictationOrchestrator {
             self.stop_level_emitter().await;
             let maybe_path = self.recording_path.lock().await.take();
             let started_at = self.recording_started_at.lock().await.take();
+            let rule = self.recording_rule.lock().await.take().unwrap_or_default();
+            let selection = self.command_selection.lock().await.take();
+            let context = self.recording_context.lock().await.take();
             drop(recorder);
 
             if let Some(path) = maybe_path {
@@ -115,14 +229,50 @@ impl DictationOrchestrator {
                         return Ok(());
                     }
                 }
-                match self.transcribe(path).await? {
-                    Some(transcript) => {
+                let stats = match analysis::analyze(&path) {
+                    Ok(stats) => Some(stats),
+                    Err(err) => {
+                        log::warn!("could not analyse recording; skipping noise guard: {err:#}");
+                        None
+                    }
+                };
+                let transcript = match self.transcribe(path, stats, &rule).await {
+                    Ok(Some(transcript)) => self.guard(transcript, stats).await,
+                    other => other,
+                };
+                let transcript = match transcript {
+                    Ok(Some(transcript)) => self.review(transcript).await,
+                    other => other,
+                };
+                match transcript {
+                    Ok(Some(transcript)) => {
                         self.emit_recording_state("processing");
-                        self.clean_and_inject(transcript).await?;
+                        let outcome = match selection {
+                            Some(selection) => {
+                                self.transform_selection(selection, transcript, &rule).await
+                            }
+                            None => {
+                                let transcript = Transcript {
+                                    context,
+                                    ..transcript
+                                };
+                                self.clean_and_inject(transcript, &rule).await
+                            }
+                        };
+                        if let Err(err) = outcome {
+                            self.set_recording_window_visible(false);
+                            return Err(err);
+                        }
+                    }
+                    Ok(None) => {
+                        self.emit_recording_state("cancelling");
+                        tokio::time::sleep(Duration::from_millis(400)).await;
                     }
-                    None => {
+                    Err(err) => {
                         self.emit_recording_state("cancelling");
                         tokio::time::sleep(Duration::from_millis(400)).await;
+                        self.set_recording_window_visible(false);
+                        return Err(err);
                     }
                 }
             }
@@ -134,19 +284,50 @@ impl DictationOrchestrator {
             "air-keys-{}.wav",
             Utc::now().format("%Y%m%d-%H%M%S")
         ));
+        self.set_pasted_candidates(None).await;
+        let rule = self.capture_app_rule().await;
+        let copy_context = rule.as_ref().is_some_and(|rule| rule.copy_context);
         self.amplitude_level.store(0.0f32.to_bits(), Ordering::Relaxed);
         recorder
             .start(temp_path.clone(), Some(self.amplitude_level.clone()))
             .context("failed to start recording")?;
         *self.recording_path.lock().await = Some(temp_path);
         *self.recording_started_at.lock().await = Some(Instant::now());
+        *self.recording_rule.lock().await = rule;
         self.set_tray_recording(true);
         self.set_recording_window_visible(true);
         self.emit_recording_state("listening");
         self.start_level_emitter().await;
+        // Selecting the line would replace a command recording's selection.
+        if self.command_selection.lock().await.is_none() {
+            *self.recording_context.lock().await = self.capture_context(copy_context).await;
+        }
         Ok(())
     }
 
+    /// Starts a command recording for the current selection, or stops whichever recording is
+    /// running. The spoken instruction is then applied to the selection and the result pasted
+    /// over it. Command mode is opt-in: right Alt is AltGr on many layouts, and its double tap
+    /// dictates like the left one unless the setting is on.
+    pub async fn handle_command_double_tap(&self) -> Result<()> {
+        if self.recorder.lock().await.is_recording() {
+            return self.handle_alt_double_tap().await;
+        }
+        if !self.key_store.read_command_mode_enabled().await? {
+            return self.handle_alt_double_tap().await;
+        }
+        let Some(selection) = self.injector.copy_selection().await? else {
+            log::info!("command mode needs selected text; nothing was copied");
+            return Ok(());
+        };
+        *self.command_selection.lock().await = Some(selection);
+        let started = self.handle_alt_double_tap().await;
+        if started.is_err() {
+            self.command_selection.lock().await.take();
+        }
+        started
+    }
+
     fn set_tray_recording(&self, is_recording: bool) {
         let Some(tray) = self.app_handle.tray_by_id(TRAY_ID) else {
             return;
@@ -214,9 +395,47 @@ impl DictationOrchestrator {
     }
 
     /// Transcribes the recording. Returns `Some(transcript)` when non-empty, `None` when empty (cancelled).
-    async fn transcribe(&self, path: PathBuf) -> Result<Option<String>> {
-        let result = self.processor.process_file(&path).await;
-        let _ = std::fs::remove_file(&path);
+    async fn transcribe(
+        &self,
+        path: PathBuf,
+        stats: Option<AudioStats>,
+        rule: &AppRule,
+    ) -> Result<Option<Transcript>> {
+        let _recording = RecordingFile(&path);
+        if let Err(err) = self.spending.check().await {
+            self.report_provider_error(&err);
+            return Err(anyhow::Error::new(err).context("transcription blocked"));
+        }
+        let chain = self
+            .registry
+            .transcription_chain(self.key_store.as_ref())
+            .await?;
+        // A translating profile names the language spoken; it outranks the app rule, which
+        // describes the app's language.
+        let translation_source = self
+            .active_profile(rule)
+            .await?
+            .translation
+            .and_then(|translation| translation.source);
+        let language = match (translation_source, &rule.language) {
+            (Some(source), _) => LanguageSetting::Forced(source),
+            (None, Some(language)) => language.clone(),
+            (None, None) => self.key_store.read_language().await?,
+        };
+        let duration = stats.map(|stats| stats.duration);
+        let result = match self.second_opinion_processor(&chain[0].0).await {
+            Some((id, processor, strategy)) => {
+                let (primary, secondary) = tokio::join!(
+                    self.transcribe_recording(chain, &path, duration, &language),
+                    processor.process_file(&path, &language)
+                );
+                self.reconcile(primary, (id, secondary), strategy).await
+            }
+            None => {
+                self.transcribe_recording(chain, &path, duration, &language)
+                    .await
+            }
+        };
 
         match result {
             Ok(transcript) => Ok(Some(transcript)),
@@ -224,42 +443,525 @@ impl DictationOrchestrator {
                 log::warn!("dictation captured but transcript was empty; skipping paste");
                 Ok(None)
             }
-            Err(err) => Err(anyhow::anyhow!("{err}")),
+            Err(err) => {
+                self.report_provider_error(&err);
+                Err(anyhow::Error::new(err).context("transcription failed"))
+            }
+        }
+    }
+
+    /// Sends the recording through the fallback chain. Long recordings are split at pauses
+    /// and their chunks transcribed in parallel.
+    async fn transcribe_recording(
+        &self,
+        chain: Vec<(String, Arc<dyn AudioProcessor>)>,
+        path: &Path,
+        duration: Option<Duration>,
+        language: &LanguageSetting,
+    ) -> Result<Transcript, AudioProcessorError> {
+        if duration.is_none_or(|duration| duration <= CHUNK_MAX) {
+            return self.fallback.transcribe(&chain, path, language).await;
+        }
+        let chunks = match splitter::split_at_silence(path, CHUNK_TARGET, CHUNK_MAX) {
+            Ok(chunks) => chunks,
+            Err(err) => {
+                log::warn!("could not split long recording; sending it whole: {err:#}");
+                return self.fallback.transcribe(&chain, path, language).await;
+            }
+        };
+        log::info!("transcribing long recording in {} chunks", chunks.len());
+        let result =
+            chunked::transcribe_chunks(self.fallback.clone(), Arc::new(chain), &chunks, language)
+                .await;
+        for chunk in &chunks {
+            let _ = std::fs::remove_file(&chunk.path);
+        }
+        result
+    }
+
+    /// Drops transcripts that the recording cannot support, such as phantom phrases produced
+    /// from silence. Returns `None` when the transcript was rejected.
+    async fn guard(
+        &self,
+        transcript: Transcript,
+        stats: Option<AudioStats>,
+    ) -> Result<Option<Transcript>> {
+        let Some(stats) = stats else {
+            return Ok(Some(transcript));
+        };
+        let blocklist = self.key_store.read_hallucination_blocklist().await?;
+        match guard::check(&transcript, &stats, &blocklist) {
+            Ok(()) => Ok(Some(transcript)),
+            Err(rejection) => {
+                log::info!(
+                    "discarded transcript from provider={}: {rejection}",
+                    transcript.provider
+                );
+                Ok(None)
+            }
+        }
+    }
+
+    /// The provider configured for a second opinion, unless it is off, unknown, or the same
+    /// as the active provider.
+    async fn second_opinion_processor(
+        &self,
+        active_id: &str,
+    ) -> Option<(String, Arc<dyn AudioProcessor>, MergeStrategy)> {
+        let settings = match self.key_store.read_second_opinion().await {
+            Ok(settings) => settings,
+            Err(err) => {
+                log::warn!("could not read second opinion settings: {err:#}");
+                return None;
+            }
+        };
+        let id = settings.provider.filter(|id| id != active_id)?;
+        match self.registry.processor(&id) {
+            Some(processor) => Some((id, processor, settings.strategy)),
+            None => {
+                log::warn!("ignoring unknown second opinion provider: {id}");
+                None
+            }
+        }
+    }
+
+    /// Combines the active provider's result with the second opinion. Either one alone is
+    /// still good enough to paste; disagreements are written to history.
+    async fn reconcile(
+        &self,
+        primary: Result<Transcript, AudioProcessorError>,
+        (secondary_id, secondary): (String, Result<Transcript, AudioProcessorError>),
+        strategy: MergeStrategy,
+    ) -> Result<Transcript, AudioProcessorError> {
+        let (primary, secondary) = match (primary, secondary) {
+            (Ok(primary), Ok(secondary)) => (primary, secondary),
+            (Ok(primary), Err(err)) => {
+                log::warn!("second opinion from {secondary_id} failed: {err}");
+                return Ok(primary);
+            }
+            (Err(err), Ok(secondary)) => {
+                log::warn!("primary transcription failed; using second opinion: {err}");
+                return Ok(secondary);
+            }
+            (Err(err), Err(_)) => return Err(err),
+        };
+
+        let record = SecondOpinionRecord {
+            primary_provider: primary.provider.clone(),
+            secondary_provider: secondary.provider.clone(),
+            primary_text: primary.text.clone(),
+            secondary_text: secondary.text.clone(),
+            disagreements: Vec::new(),
+        };
+        let outcome = merge(primary, secondary, strategy);
+        if !outcome.disagreements.is_empty() {
+            log::info!(
+                "second opinion disagreed at {} positions",
+                outcome.disagreements.len()
+            );
+            let entry = HistoryEntry {
+                timestamp: Utc::now().to_rfc3339(),
+                provider: outcome.transcript.provider.clone(),
+                text: outcome.transcript.text.clone(),
+                second_opinion: Some(SecondOpinionRecord {
+                    disagreements: outcome.disagreements,
+                    ..record
+                }),
+            };
+            if let Err(err) = self.history.append(&entry).await {
+                log::warn!("failed to record second opinion in history: {err:#}");
+            }
+        }
+        Ok(outcome.transcript)
+    }
+
+    pub fn submit_review_decision(&self, decision: ReviewDecision) -> Result<()> {
+        self.review_gate.resolve(decision)
+    }
+
+    /// When review mode is on and some words fall below the confidence threshold, shows the
+    /// transcript in the overlay and waits for the user. Returns `None` if they cancel or the
+    /// review times out.
+    async fn review(&self, transcript: Transcript) -> Result<Option<Transcript>> {
+        if !transcript.has_words_below(LOW_CONFIDENCE_THRESHOLD)
+            || !self.key_store.read_review_enabled().await?
+        {
+            return Ok(Some(transcript));
+        }
+
+        let decision = self.review_gate.open();
+        self.set_recording_window_size(REVIEW_WINDOW_SIZE);
+        self.set_recording_window_visible(true);
+        if let Some(window) = self.app_handle.get_webview_window(RECORDING_WINDOW_ID) {
+            let _ = window.set_focus();
+            let _ = window.emit(
+                REVIEW_REQUESTED_EVENT,
+                ReviewRequestPayload {
+                    transcript: &transcript,
+                    threshold: LOW_CONFIDENCE_THRESHOLD,
+                },
+            );
+        }
+        let outcome = tokio::time::timeout(REVIEW_TIMEOUT, decision).await;
+
+        // The overlay took focus for the keyboard shortcuts; hide it so the paste lands in the
+        // app the user was dictating into.
+        self.set_recording_window_visible(false);
+        self.set_recording_window_size(RECORDING_WINDOW_SIZE);
+        tokio::time::sleep(Duration::from_millis(REVIEW_FOCUS_RESTORE_MS)).await;
+
+        match outcome {
+            Ok(Ok(ReviewDecision::Accept { text })) => Ok(Some(Transcript { text, ..transcript })),
+            Ok(Ok(ReviewDecision::Cancel)) | Ok(Err(_)) => {
+                log::info!("review cancelled; discarding transcript");
+                Ok(None)
+            }
+            Err(_) => {
+                log::info!("review timed out; discarding transcript");
+                Ok(None)
+            }
         }
     }
 
-    #[instrument(skip(self, transcript), fields(transcript_len = transcript.len()))]
-    async fn clean_and_inject(&self, transcript: String) -> Result<()> {
+    fn set_recording_window_size(&self, (width, height): (f64, f64)) {
+        if let Some(window) = self.app_handle.get_webview_window(RECORDING_WINDOW_ID) {
+            let _ = window.set_size(LogicalSize::new(width, height));
+        }
+    }
+
+    /// Tells the UI which provider failed and how; credential problems and a reached spending
+    /// limit also bring up settings so the user can act on them.
+    fn report_provider_error(&self, err: &AudioProcessorError) {
+        let _ = self.app_handle.emit(
+            PROVIDER_ERROR_EVENT,
+            ProviderErrorPayload {
+                kind: err.kind(),
+                provider: err.provider(),
+                message: err.to_string(),
+            },
+        );
+        if matches!(
+            err,
+            AudioProcessorError::Unauthorized { .. }
+                | AudioProcessorError::SpendingLimitReached { .. }
+        ) {
+            if let Some(window) = self.app_handle.get_webview_window(SETTINGS_WINDOW_ID) {
+                let _ = window.show();
+                let _ = window.set_focus();
+            }
+        }
+    }
+
+    /// The profile named by the app rule, or else the one selected in settings or the tray.
+    async fn active_profile(&self, rule: &AppRule) -> Result<CleanupProfile> {
+        let profiles = self.key_store.read_cleanup_profiles().await?;
+        let active_id = match &rule.profile {
+            Some(id) if profiles.iter().any(|profile| &profile.id == id) => id.clone(),
+            _ => self.key_store.read_active_profile().await?,
+        };
+        profile::resolve(&profiles, &active_id)
+            .cloned()
+            .context("no cleanup profiles are configured")
+    }
+
+    #[instrument(skip(self, transcript, rule), fields(transcript_len = transcript.text.len()))]
+    async fn clean_and_inject(&self, transcript: Transcript, rule: &AppRule) -> Result<()> {
         let total_start = Instant::now();
-        let should_clean = self.key_store.read_processing_enabled().await?;
+        let profile = self.active_profile(rule).await?;
         let clean_start = Instant::now();
-        let transcript_to_inject = if should_clean {
-            match self.cleaner.clean(&transcript).await {
-                Ok(cleaned) => cleaned,
-                Err(AudioProcessorError::MissingGeminiApiKey) => {
-                    log::warn!("post-processing enabled but Gemini API key is missing");
-                    transcript
-                }
-                Err(err) => {
-                    log::warn!("post-processing failed; using raw transcript: {err}");
-                    transcript
+
+        let run = match self.cleanup.run(&transcript, &profile).await {
+            Ok(run) => run,
+            Err(aborted) => {
+                if aborted.stage.uses_provider() {
+                    self.report_provider_error(&aborted.source);
                 }
+                return Err(anyhow::Error::new(aborted).context("dictation was not pasted"));
+            }
+        };
+        for (stage, err) in &run.skipped {
+            log::warn!("cleanup stage {} skipped: {err}", stage.as_str());
+            let missing_key = matches!(err, AudioProcessorError::MissingGeminiApiKey);
+            if stage.uses_provider() && !missing_key {
+                self.report_provider_error(err);
             }
+        }
+        let transcript_to_inject = if run.llm_applied {
+            profile.output.apply(&run.text)
         } else {
-            transcript
+            run.text.clone()
         };
         let clean_duration = clean_start.elapsed();
+        let suffix = if rule.trailing_space { " " } else { "" };
 
         let inject_start = Instant::now();
-        self.injector.inject_text(&transcript_to_inject).await?;
+        self.injector
+            .inject_text(&format!("{transcript_to_inject}{suffix}"), rule.injection)
+            .await?;
         let inject_duration = inject_start.elapsed();
+        self.remember_candidates(transcript_to_inject, transcript, suffix, rule.injection)
+            .await;
         let total_duration = total_start.elapsed();
         log::info!(
-            "clean_and_inject completed total={}ms clean={}ms inject={}ms",
+            "clean_and_inject completed total={}ms clean={}ms inject={}ms stages={}",
             total_duration.as_millis(),
             clean_duration.as_millis(),
-            inject_duration.as_millis()
+            inject_duration.as_millis(),
+            run.timings_summary()
+        );
+        Ok(())
+    }
+
+    /// Rewrites `selection` as the spoken `instruction` asks, using the cleanup provider with the
+    /// transform prompt, and pastes the result over the selection, which is still selected.
+    #[instrument(skip_all, fields(selection_len = selection.len()))]
+    async fn transform_selection(
+        &self,
+        selection: String,
+        instruction: Transcript,
+        rule: &AppRule,
+    ) -> Result<()> {
+        let total_start = Instant::now();
+        let profile = self.active_profile(rule).await?;
+        if let Err(err) = self.spending.check().await {
+            self.report_provider_error(&err);
+            return Err(anyhow::Error::new(err).context("selection was not transformed"));
+        }
+        let (_, cleaner) = self
+            .registry
+            .active_cleaner(self.key_store.as_ref())
+            .await?;
+        let timeout = self
+            .key_store
+            .read_cleanup_pipeline()
+            .await?
+            .iter()
+            .find(|stage| stage.kind == StageKind::Llm)
+            .map_or(TRANSFORM_TIMEOUT, |stage| {
+                Duration::from_millis(stage.timeout_ms)
+            });
+        let command_profile = CleanupProfile {
+            prompt: cleanup_prompt::TRANSFORM_INSTRUCTION.to_string(),
+            ..profile
+        };
+        let input = Transcript {
+            text: cleanup_prompt::transform_input(&selection, &instruction.text),
+            language: None,
+            ..instruction
+        };
+
+        let clean_start = Instant::now();
+        let result = tokio::time::timeout(timeout, cleaner.clean(&input, &command_profile))
+            .await
+            .unwrap_or(Err(AudioProcessorError::Timeout {
+                provider: "transform",
+            }));
+        let transformed = match result {
+            // The output guard hands back the input when the model rambles.
+            Ok(text) if !text.trim().is_empty() && text != input.text => text,
+            Ok(_) => bail!("the provider returned no usable rewrite; the selection was kept"),
+            Err(err) => {
+                self.report_provider_error(&err);
+                return Err(anyhow::Error::new(err).context("selection was not transformed"));
+            }
+        };
+        let clean_duration = clean_start.elapsed();
+
+        self.injector
+            .inject_text(&transformed, rule.injection)
+            .await?;
+        let transformed_len = transformed.len();
+        // The alternative hotkey can put the original text back.
+        self.set_pasted_candidates(Some(PastedCandidates {
+            candidates: vec![transformed, selection],
+            pasted: 0,
+            selected: 0,
+            seq: 0,
+            method: rule.injection,
+        }))
+        .await;
+        log::info!(
+            "transform_selection completed total={}ms clean={}ms result_len={}",
+            total_start.elapsed().as_millis(),
+            clean_duration.as_millis(),
+            transformed_len
+        );
+        Ok(())
+    }
+
+    /// Keeps the pasted text plus the uncleaned transcript and provider runner-ups so the
+    /// alternative hotkey can swap them in. `suffix` is whatever was injected after the text.
+    async fn remember_candidates(
+        &self,
+        pasted: String,
+        transcript: Transcript,
+        suffix: &str,
+        method: InjectionMethod,
+    ) {
+        let mut candidates = vec![pasted];
+        for candidate in std::iter::once(transcript.text).chain(transcript.alternatives) {
+            if !candidates.contains(&candidate) {
+                candidates.push(candidate);
+            }
+        }
+        self.set_pasted_candidates(Some(PastedCandidates {
+            candidates: candidates
+                .into_iter()
+                .map(|candidate| format!("{candidate}{suffix}"))
+                .collect(),
+            pasted: 0,
+            selected: 0,
+            seq: 0,
+            method,
+        }))
+        .await;
+    }
+
+    async fn set_pasted_candidates(&self, candidates: Option<PastedCandidates>) {
+        let mut guard = self.pasted_candidates.lock().await;
+        self.alternatives_available.store(
+            candidates.as_ref().is_some_and(PastedCandidates::pickable),
+            Ordering::Relaxed,
+        );
+        *guard = candidates;
+    }
+
+    /// Whether Alt+` has a pasted dictation to swap; cheap enough for the keyboard hook.
+    pub fn has_alternatives(&self) -> bool {
+        self.alternatives_available.load(Ordering::Relaxed)
+    }
+
+    /// Detects the focused application and returns the first app rule that matches it.
+    async fn capture_app_rule(&self) -> Option<AppRule> {
+        let app = match tauri::async_runtime::spawn_blocking(foreground::capture).await {
+            Ok(Ok(app)) => app,
+            Ok(Err(err)) => {
+                log::info!("could not detect the foreground app; using global settings: {err:#}");
+                return None;
+            }
+            Err(err) => {
+                log::warn!("foreground app detection failed: {err}");
+                return None;
+            }
+        };
+        *self.last_foreground_app.lock().await = Some(app.clone());
+        let app_rules = match self.key_store.read_app_rules().await {
+            Ok(app_rules) => app_rules,
+            Err(err) => {
+                log::warn!("could not read app rules: {err:#}");
+                return None;
+            }
+        };
+        let rule = rules::matching(&app_rules, &app).cloned();
+        log::info!(
+            "foreground app process={} class={} rule_matched={}",
+            app.process_name,
+            app.window_class,
+            rule.is_some()
         );
+        rule
+    }
+
+    /// Reads the text before the caret for the cleanup stages when context capture is on,
+    /// through the accessibility api or else, for app rules that opt in with `copy_context`, by
+    /// copying the line with Shift+Home.
+    async fn capture_context(&self, copy_context: bool) -> Option<String> {
+        match self.key_store.read_context_capture_enabled().await {
+            Ok(true) => {}
+            Ok(false) => return None,
+            Err(err) => {
+                log::warn!("could not read context capture setting: {err:#}");
+                return None;
+            }
+        }
+        let context = match tauri::async_runtime::spawn_blocking(caret_context::capture).await {
+            Ok(Ok(text)) => Some(text),
+            Ok(Err(err)) if copy_context => {
+                log::info!("caret text not readable; copying the line instead: {err:#}");
+                match self.injector.copy_before_caret().await {
+                    Ok(text) => text,
+                    Err(err) => {
+                        log::warn!("could not copy the text before the caret: {err:#}");
+                        None
+                    }
+                }
+            }
+            Ok(Err(err)) => {
+                log::info!("no caret context for this app: {err:#}");
+                None
+            }
+            Err(err) => {
+                log::warn!("caret context capture failed: {err}");
+                None
+            }
+        };
+        let context = context.filter(|text| !text.trim().is_empty())?;
+        let context = caret_context::tail(&context).to_string();
+        log::info!("caret context captured context_len={}", context.len());
+        Some(context)
+    }
+
+    /// The application focused when the last recording started, so settings can offer it as
+    /// a rule template.
+    pub async fn last_foreground_app(&self) -> Option<ForegroundApp> {
+        self.last_foreground_app.lock().await.clone()
+    }
+
+    /// Moves the selection to the next candidate for the last paste and shows the list in the
+    /// overlay. Each press restarts the commit delay; once it elapses the pasted text is
+    /// replaced with the selection.
+    pub async fn cycle_alternative(&self) -> Result<()> {
+        if self.recorder.lock().await.is_recording() {
+            return Ok(());
+        }
+        let seq = {
+            let mut guard = self.pasted_candidates.lock().await;
+            let Some(pasted) = guard.as_mut().filter(|pasted| pasted.candidates.len() > 1) else {
+                log::info!("no alternatives available for the last dictation");
+                return Ok(());
+            };
+            if clipboard_injector::is_multiline(&pasted.candidates[pasted.pasted]) {
+                log::info!("alternatives unavailable: the pasted text spans several lines");
+                return Ok(());
+            }
+            pasted.selected = (pasted.selected + 1) % pasted.candidates.len();
+            pasted.seq = pasted.seq.wrapping_add(1);
+            self.set_recording_window_size(ALTERNATIVES_WINDOW_SIZE);
+            self.set_recording_window_visible(true);
+            if let Some(window) = self.app_handle.get_webview_window(RECORDING_WINDOW_ID) {
+                let _ = window.emit(
+                    ALTERNATIVES_EVENT,
+                    AlternativesPayload {
+                        candidates: &pasted.candidates,
+                        selected: pasted.selected,
+                    },
+                );
+            }
+            pasted.seq
+        };
+
+        tokio::time::sleep(ALTERNATIVE_COMMIT_DELAY).await;
+
+        let mut guard = self.pasted_candidates.lock().await;
+        let Some(pasted) = guard.as_mut().filter(|pasted| pasted.seq == seq) else {
+            return Ok(());
+        };
+        self.set_recording_window_visible(false);
+        self.set_recording_window_size(RECORDING_WINDOW_SIZE);
+        if pasted.selected == pasted.pasted {
+            return Ok(());
+        }
+        self.injector
+            .replace_previous(
+                &pasted.candidates[pasted.pasted],
+                &pasted.candidates[pasted.selected],
+                pasted.method,
+            )
+            .await?;
+        pasted.pasted = pasted.selected;
+        self.alternatives_available
+            .store(pasted.pickable(), Ordering::Relaxed);
+        log::info!("swapped pasted text for alternative {}", pasted.selected);
         Ok(())
     }
 }
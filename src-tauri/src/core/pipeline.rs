@@ -0,0 +1,276 @@
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+use serde::{Deserialize, Serialize};
+
+use crate::core::audio_processor::{AudioProcessorError, TranscriptCleaner};
+use crate::core::profile::CleanupProfile;
+use crate::core::transcript::Transcript;
+use crate::processors::casing::CasingCleaner;
+use crate::processors::code_mode::CodeModeCleaner;
+use crate::processors::registry::ProviderRegistry;
+use crate::processors::replacements::ReplacementsCleaner;
+use crate::processors::spoken_punctuation::SpokenPunctuationCleaner;
+use crate::processors::translation::TranslationCleaner;
+use crate::settings::stronghold_store::SecureKeyStore;
+use crate::usage::quota::SpendingLimit;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum StageKind {
+    Replacements,
+    SpokenPunctuation,
+    /// Only acts for profiles with code mode on.
+    Code,
+    /// The post-processing provider selected in settings. Skipped for translating profiles,
+    /// whose translation prompt cleans as well.
+    Llm,
+    /// Only acts for profiles with a translation target.
+    Translate,
+    Casing,
+}
+
+impl StageKind {
+    pub const ALL: [StageKind; 6] = [
+        StageKind::Replacements,
+        StageKind::SpokenPunctuation,
+        StageKind::Code,
+        StageKind::Llm,
+        StageKind::Translate,
+        StageKind::Casing,
+    ];
+
+    pub fn as_str(self) -> &'static str {
+        match self {
+            StageKind::Replacements => "replacements",
+            StageKind::SpokenPunctuation => "spoken_punctuation",
+            StageKind::Code => "code",
+            StageKind::Llm => "llm",
+            StageKind::Translate => "translate",
+            StageKind::Casing => "casing",
+        }
+    }
+
+    /// Whether the stage calls a cleanup provider, whose errors are shown to the user.
+    pub fn uses_provider(self) -> bool {
+        matches!(self, StageKind::Llm | StageKind::Translate)
+    }
+
+    fn default_timeout_ms(self) -> u64 {
+        match self {
+            StageKind::Llm | StageKind::Translate => 30_000,
+            StageKind::Replacements
+            | StageKind::SpokenPunctuation
+            | StageKind::Code
+            | StageKind::Casing => 1_000,
+        }
+    }
+}
+
+/// What happens to the dictation when a stage fails or times out.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum FailurePolicy {
+    /// Pass the text on unchanged to the next stage.
+    #[default]
+    Skip,
+    /// Stop and paste nothing.
+    Abort,
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CleanupStage {
+    pub kind: StageKind,
+    pub enabled: bool,
+    pub timeout_ms: u64,
+    #[serde(default)]
+    pub on_failure: FailurePolicy,
+}
+
+impl CleanupStage {
+    fn new(kind: StageKind, enabled: bool) -> Self {
+        Self {
+            kind,
+            enabled,
+            timeout_ms: kind.default_timeout_ms(),
+            on_failure: FailurePolicy::Skip,
+        }
+    }
+}
+
+/// The stages a fresh install runs, in order. `llm_enabled` carries over the old
+/// post-processing toggle.
+pub fn default_stages(llm_enabled: bool) -> Vec<CleanupStage> {
+    vec![
+        CleanupStage::new(StageKind::Replacements, true),
+        CleanupStage::new(StageKind::SpokenPunctuation, false),
+        CleanupStage::new(StageKind::Code, true),
+        CleanupStage::new(StageKind::Llm, llm_enabled),
+        CleanupStage::new(StageKind::Translate, true),
+        CleanupStage::new(StageKind::Casing, false),
+    ]
+}
+
+/// Adds stages introduced after `stages` was saved, at their default position.
+pub fn with_missing_stages(mut stages: Vec<CleanupStage>, llm_enabled: bool) -> Vec<CleanupStage> {
+    for (index, stage) in default_stages(llm_enabled).into_iter().enumerate() {
+        if !stages.iter().any(|existing| existing.kind == stage.kind) {
+            stages.insert(index.min(stages.len()), stage);
+        }
+    }
+    stages
+}
+
+/// The text a pipeline produced, how long each stage took and the errors of skipped stages.
+pub struct PipelineRun {
+    pub text: String,
+    pub timings: Vec<(StageKind, Duration)>,
+    pub skipped: Vec<(StageKind, AudioProcessorError)>,
+    /// Whether the post-processing or translation provider's output made it through.
+    pub llm_applied: bool,
+}
+
+impl PipelineRun {
+    /// `spoken_punctuation=3ms,llm=812ms` for the `clean_and_inject completed` log line.
+    pub fn timings_summary(&self) -> String {
+        self.timings
+            .iter()
+            .map(|(kind, duration)| format!("{}={}ms", kind.as_str(), duration.as_millis()))
+            .collect::<Vec<_>>()
+            .join(",")
+    }
+}
+
+#[derive(Debug, thiserror::Error)]
+#[error("cleanup stage {} failed: {source}", stage.as_str())]
+pub struct StageAborted {
+    pub stage: StageKind,
+    #[source]
+    pub source: AudioProcessorError,
+}
+
+/// Runs the configured cleanup stages in order, each on the previous stage's output.
+pub struct CleanupPipeline {
+    key_store: Arc<dyn SecureKeyStore>,
+    registry: Arc<ProviderRegistry>,
+    replacements: Arc<ReplacementsCleaner>,
+    spoken_punctuation: Arc<SpokenPunctuationCleaner>,
+    code: Arc<CodeModeCleaner>,
+    translation: Arc<TranslationCleaner>,
+    casing: Arc<CasingCleaner>,
+    spending: Arc<SpendingLimit>,
+}
+
+impl CleanupPipeline {
+    pub fn new(
+        key_store: Arc<dyn SecureKeyStore>,
+        registry: Arc<ProviderRegistry>,
+        spending: Arc<SpendingLimit>,
+    ) -> Self {
+        Self {
+            replacements: Arc::new(ReplacementsCleaner::new(key_store.clone())),
+            spoken_punctuation: Arc::new(SpokenPunctuationCleaner::new(key_store.clone())),
+            code: Arc::new(CodeModeCleaner::new()),
+            translation: Arc::new(TranslationCleaner::new(key_store.clone(), registry.clone())),
+            casing: Arc::new(CasingCleaner::new()),
+            key_store,
+            registry,
+            spending,
+        }
+    }
+
+    async fn cleaner(
+        &self,
+        kind: StageKind,
+    ) -> Result<Arc<dyn TranscriptCleaner>, AudioProcessorError> {
+        match kind {
+            StageKind::Replacements => Ok(self.replacements.clone()),
+            StageKind::SpokenPunctuation => Ok(self.spoken_punctuation.clone()),
+            StageKind::Code => Ok(self.code.clone()),
+            StageKind::Translate => Ok(self.translation.clone()),
+            StageKind::Casing => Ok(self.casing.clone()),
+            StageKind::Llm => self
+                .registry
+                .active_cleaner(self.key_store.as_ref())
+                .await
+                .map(|(_, cleaner)| cleaner)
+                .map_err(|err| AudioProcessorError::Request(format!("{err:#}"))),
+        }
+    }
+
+    /// The stage's cleaner, unless the stage would bill a provider past a hard spending limit.
+    async fn billed_cleaner(
+        &self,
+        kind: StageKind,
+        billed: bool,
+    ) -> Result<Arc<dyn TranscriptCleaner>, AudioProcessorError> {
+        if billed {
+            self.spending.check().await?;
+        }
+        self.cleaner(kind).await
+    }
+
+    pub async fn run(
+        &self,
+        transcript: &Transcript,
+        profile: &CleanupProfile,
+    ) -> Result<PipelineRun, StageAborted> {
+        let stages = self
+            .key_store
+            .read_cleanup_pipeline()
+            .await
+            .map_err(|err| StageAborted {
+                stage: StageKind::Llm,
+                source: AudioProcessorError::Request(format!("{err:#}")),
+            })?;
+        let mut working = transcript.clone();
+        let mut run = PipelineRun {
+            text: String::new(),
+            timings: Vec::new(),
+            skipped: Vec::new(),
+            llm_applied: false,
+        };
+
+        let translating = profile.translation.is_some();
+        for stage in stages.iter().filter(|stage| stage.enabled) {
+            if stage.kind == StageKind::Llm && translating {
+                continue;
+            }
+            // The translate stage passes text through untouched for other profiles.
+            let billed =
+                stage.kind == StageKind::Llm || (stage.kind == StageKind::Translate && translating);
+            let start = Instant::now();
+            let result = match self.billed_cleaner(stage.kind, billed).await {
+                Ok(cleaner) => tokio::time::timeout(
+                    Duration::from_millis(stage.timeout_ms),
+                    cleaner.clean(&working, profile),
+                )
+                .await
+                .unwrap_or(Err(AudioProcessorError::Timeout {
+                    provider: stage.kind.as_str(),
+                })),
+                Err(err) => Err(err),
+            };
+            run.timings.push((stage.kind, start.elapsed()));
+            match result {
+                Ok(text) => {
+                    working.text = text;
+                    run.llm_applied |= billed;
+                }
+                Err(err) => match stage.on_failure {
+                    FailurePolicy::Skip => run.skipped.push((stage.kind, err)),
+                    FailurePolicy::Abort => {
+                        return Err(StageAborted {
+                            stage: stage.kind,
+                            source: err,
+                        })
+                    }
+                },
+            }
+        }
+
+        run.text = working.text;
+        Ok(run)
+    }
+}
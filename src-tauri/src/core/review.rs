@@ -0,0 +1,76 @@
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+use anyhow::{anyhow, Result};
+use serde::{Deserialize, Serialize};
+use tauri::State;
+use tokio::sync::oneshot;
+
+use crate::core::orchestrator::DictationOrchestrator;
+use crate::core::transcript::Transcript;
+
+/// Words the provider is less sure about than this are highlighted for review.
+pub const LOW_CONFIDENCE_THRESHOLD: f64 = 0.7;
+/// How long a review may stay open before the dictation is dropped.
+pub const REVIEW_TIMEOUT: Duration = Duration::from_secs(120);
+
+#[derive(Debug, Deserialize)]
+#[serde(tag = "action", rename_all = "snake_case")]
+pub enum ReviewDecision {
+    /// Paste `text`, which includes any alternatives the user picked.
+    Accept {
+        text: String,
+    },
+    Cancel,
+}
+
+#[derive(Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ReviewRequestPayload<'a> {
+    pub transcript: &'a Transcript,
+    pub threshold: f64,
+}
+
+/// Hands the overlay's decision back to the dictation waiting on it. Only one review can be
+/// open; opening another drops the previous sender, which the old waiter treats as a cancel.
+pub struct ReviewGate {
+    pending: Mutex<Option<oneshot::Sender<ReviewDecision>>>,
+}
+
+impl ReviewGate {
+    pub fn new() -> Self {
+        Self {
+            pending: Mutex::new(None),
+        }
+    }
+
+    pub fn open(&self) -> oneshot::Receiver<ReviewDecision> {
+        let (sender, receiver) = oneshot::channel();
+        if let Ok(mut pending) = self.pending.lock() {
+            *pending = Some(sender);
+        }
+        receiver
+    }
+
+    pub fn resolve(&self, decision: ReviewDecision) -> Result<()> {
+        let sender = self
+            .pending
+            .lock()
+            .map_err(|_| anyhow!("review state is poisoned"))?
+            .take()
+            .ok_or_else(|| anyhow!("no review is pending"))?;
+        sender
+            .send(decision)
+            .map_err(|_| anyhow!("the dictation is no longer waiting for a review"))
+    }
+}
+
+#[tauri::command]
+pub fn submit_review_decision(
+    orchestrator: State<'_, Arc<DictationOrchestrator>>,
+    decision: ReviewDecision,
+) -> Result<(), String> {
+    orchestrator
+        .submit_review_decision(decision)
+        .map_err(|err| format!("failed to submit review decision: {err}"))
+}
@@ -0,0 +1,56 @@
+use serde::Serialize;
+
+#[derive(Clone, Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TranscriptWord {
+    /// The word as it should appear in text, including punctuation and casing.
+    pub text: String,
+    /// Offsets into the recording, in seconds.
+    pub start: f64,
+    pub end: f64,
+    /// Provider confidence in `0.0..=1.0`, when the provider reports one.
+    pub confidence: Option<f64>,
+    /// Other spellings the provider considered for the same stretch of audio.
+    pub alternatives: Vec<String>,
+}
+
+/// A transcription result with everything the provider told us about it, so features beyond
+/// plain text don't have to re-parse provider payloads.
+#[derive(Clone, Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Transcript {
+    pub text: String,
+    pub words: Vec<TranscriptWord>,
+    /// Runner-up transcripts of the whole recording, best first, without `text` itself.
+    pub alternatives: Vec<String>,
+    /// BCP-47 code of the detected (or requested) language.
+    pub language: Option<String>,
+    pub provider: String,
+    pub request_id: Option<String>,
+    /// Text that was before the caret when the dictation started. Cleaners may match its
+    /// capitalization, spacing and terminology but never output it.
+    #[serde(skip)]
+    pub context: Option<String>,
+}
+
+impl Transcript {
+    /// Mean confidence over the words that carry one.
+    pub fn average_confidence(&self) -> Option<f64> {
+        let confidences: Vec<f64> = self
+            .words
+            .iter()
+            .filter_map(|word| word.confidence)
+            .collect();
+        if confidences.is_empty() {
+            return None;
+        }
+        Some(confidences.iter().sum::<f64>() / confidences.len() as f64)
+    }
+
+    pub fn has_words_below(&self, threshold: f64) -> bool {
+        self.words.iter().any(|word| {
+            word.confidence
+                .is_some_and(|confidence| confidence < threshold)
+        })
+    }
+}
@@ -0,0 +1,81 @@
+use anyhow::Result;
+
+/// How much text before the caret is kept as cleanup context.
+pub const MAX_CHARS: usize = 500;
+
+#[cfg(target_os = "windows")]
+mod platform {
+    use anyhow::{bail, Context, Result};
+    use windows::Win32::System::Com::{
+        CoCreateInstance, CoInitializeEx, CLSCTX_INPROC_SERVER, COINIT_MULTITHREADED,
+    };
+    use windows::Win32::UI::Accessibility::{
+        CUIAutomation, IUIAutomation, IUIAutomationTextPattern, TextPatternRangeEndpoint_End,
+        TextPatternRangeEndpoint_Start, TextUnit_Character, UIA_TextPatternId,
+    };
+
+    use super::MAX_CHARS;
+
+    /// Reads the text before the caret through the focused element's UI Automation text
+    /// pattern, which standard edit controls, browsers and most editors expose. Password
+    /// fields are never read.
+    pub fn capture() -> Result<String> {
+        unsafe {
+            CoInitializeEx(None, COINIT_MULTITHREADED)
+                .ok()
+                .context("could not initialise COM")?;
+            let automation: IUIAutomation =
+                CoCreateInstance(&CUIAutomation, None, CLSCTX_INPROC_SERVER)
+                    .context("could not start UI Automation")?;
+            let element = automation
+                .GetFocusedElement()
+                .context("no focused element")?;
+            if element.CurrentIsPassword()?.as_bool() {
+                bail!("the focused element is a password field");
+            }
+            let pattern: IUIAutomationTextPattern = element
+                .GetCurrentPatternAs(UIA_TextPatternId)
+                .context("the focused element does not expose its text")?;
+            let selections = pattern.GetSelection()?;
+            if selections.Length()? == 0 {
+                bail!("the focused element has no caret");
+            }
+            let caret = selections.GetElement(0)?;
+            let before = caret.Clone()?;
+            before.MoveEndpointByRange(
+                TextPatternRangeEndpoint_End,
+                &caret,
+                TextPatternRangeEndpoint_Start,
+            )?;
+            before.MoveEndpointByUnit(
+                TextPatternRangeEndpoint_Start,
+                TextUnit_Character,
+                -(MAX_CHARS as i32),
+            )?;
+            Ok(before.GetText(MAX_CHARS as i32)?.to_string())
+        }
+    }
+}
+
+#[cfg(not(target_os = "windows"))]
+mod platform {
+    use anyhow::{bail, Result};
+
+    pub fn capture() -> Result<String> {
+        bail!("reading text through the accessibility api is not supported on this platform")
+    }
+}
+
+/// Reads the text before the caret in the focused control without touching it. Errors mean
+/// the control does not expose its text this way; callers may fall back to the clipboard.
+pub fn capture() -> Result<String> {
+    platform::capture()
+}
+
+/// The last `MAX_CHARS` characters of `text`.
+pub fn tail(text: &str) -> &str {
+    match text.char_indices().rev().nth(MAX_CHARS - 1) {
+        Some((index, _)) => &text[index..],
+        None => text,
+    }
+}
@@ -0,0 +1,132 @@
+use anyhow::Result;
+use serde::{Deserialize, Serialize};
+
+/// The application that had keyboard focus when a dictation started.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ForegroundApp {
+    /// Executable name, e.g. `WindowsTerminal.exe` or `code`.
+    pub process_name: String,
+    /// Win32 window class or X11 `WM_CLASS` class name.
+    pub window_class: String,
+}
+
+#[cfg(target_os = "windows")]
+mod platform {
+    use anyhow::{bail, Context, Result};
+    use windows::core::PWSTR;
+    use windows::Win32::Foundation::CloseHandle;
+    use windows::Win32::System::Threading::{
+        OpenProcess, QueryFullProcessImageNameW, PROCESS_NAME_WIN32,
+        PROCESS_QUERY_LIMITED_INFORMATION,
+    };
+    use windows::Win32::UI::WindowsAndMessaging::{
+        GetClassNameW, GetForegroundWindow, GetWindowThreadProcessId,
+    };
+
+    use super::ForegroundApp;
+
+    pub fn capture() -> Result<ForegroundApp> {
+        let hwnd = unsafe { GetForegroundWindow() };
+        if hwnd.is_invalid() {
+            bail!("no foreground window");
+        }
+
+        let mut class_buf = [0u16; 256];
+        let class_len = unsafe { GetClassNameW(hwnd, &mut class_buf) };
+        let window_class = String::from_utf16_lossy(&class_buf[..class_len.max(0) as usize]);
+
+        let mut process_id = 0u32;
+        unsafe { GetWindowThreadProcessId(hwnd, Some(&mut process_id as *mut u32)) };
+        let process = unsafe { OpenProcess(PROCESS_QUERY_LIMITED_INFORMATION, false, process_id) }
+            .context("could not open foreground process")?;
+        let mut path_buf = [0u16; 1024];
+        let mut path_len = path_buf.len() as u32;
+        let queried = unsafe {
+            QueryFullProcessImageNameW(
+                process,
+                PROCESS_NAME_WIN32,
+                PWSTR(path_buf.as_mut_ptr()),
+                &mut path_len,
+            )
+        };
+        let _ = unsafe { CloseHandle(process) };
+        queried.context("could not read foreground process name")?;
+        let path = String::from_utf16_lossy(&path_buf[..path_len as usize]);
+        let process_name = path.rsplit('\\').next().unwrap_or_default().to_string();
+
+        Ok(ForegroundApp {
+            process_name,
+            window_class,
+        })
+    }
+}
+
+#[cfg(target_os = "linux")]
+mod platform {
+    use anyhow::{Context, Result};
+    use x11rb::connection::Connection;
+    use x11rb::properties::WmClass;
+    use x11rb::protocol::xproto::{AtomEnum, ConnectionExt, Window};
+
+    use super::ForegroundApp;
+
+    /// Reads `_NET_ACTIVE_WINDOW` from the root window, then that window's `WM_CLASS` and
+    /// `_NET_WM_PID`. Needs an EWMH window manager; Wayland sessions only expose XWayland
+    /// windows this way.
+    pub fn capture() -> Result<ForegroundApp> {
+        let (conn, screen) = x11rb::connect(None).context("could not connect to X server")?;
+        let root = conn.setup().roots[screen].root;
+        let active_atom = intern(&conn, b"_NET_ACTIVE_WINDOW")?;
+        let pid_atom = intern(&conn, b"_NET_WM_PID")?;
+
+        let window: Window = conn
+            .get_property(false, root, active_atom, AtomEnum::WINDOW, 0, 1)?
+            .reply()?
+            .value32()
+            .and_then(|mut values| values.next())
+            .filter(|&window| window != 0)
+            .context("no active window")?;
+
+        let window_class = WmClass::get(&conn, window)?
+            .reply()
+            .ok()
+            .flatten()
+            .map(|class| String::from_utf8_lossy(class.class()).into_owned())
+            .unwrap_or_default();
+        let process_name = conn
+            .get_property(false, window, pid_atom, AtomEnum::CARDINAL, 0, 1)?
+            .reply()?
+            .value32()
+            .and_then(|mut values| values.next())
+            .and_then(|pid| std::fs::read_to_string(format!("/proc/{pid}/comm")).ok())
+            .map(|name| name.trim().to_string())
+            .unwrap_or_default();
+
+        Ok(ForegroundApp {
+            process_name,
+            window_class,
+        })
+    }
+
+    fn intern(conn: &impl Connection, name: &[u8]) -> Result<u32> {
+        Ok(conn.intern_atom(false, name)?.reply()?.atom)
+    }
+}
+
+#[cfg(not(any(target_os = "windows", target_os = "linux")))]
+mod platform {
+    use anyhow::{bail, Result};
+
+    use super::ForegroundApp;
+
+    pub fn capture() -> Result<ForegroundApp> {
+        bail!("foreground window detection is not supported on this platform")
+    }
+}
+
+/// Looks up the focused application. Detection is best-effort: callers should treat an error
+/// as "no app-specific rules apply".
+pub fn capture() -> Result<ForegroundApp> {
+    platform::capture()
+}
@@ -0,0 +1,3 @@
+pub mod caret_context;
+pub mod foreground;
+pub mod rules;
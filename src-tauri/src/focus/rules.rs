@@ -0,0 +1,59 @@
+use serde::{Deserialize, Serialize};
+
+use crate::core::language::LanguageSetting;
+use crate::focus::foreground::ForegroundApp;
+use crate::injection::clipboard_injector::InjectionMethod;
+
+/// Overrides applied while dictating into a matching application. An empty profile or language
+/// falls back to the global setting.
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct AppRule {
+    /// Matched case-insensitively against the executable name; `.exe` is optional.
+    pub process_name: Option<String>,
+    /// Matched case-insensitively against the window class.
+    pub window_class: Option<String>,
+    pub profile: Option<String>,
+    pub language: Option<LanguageSetting>,
+    pub injection: InjectionMethod,
+    /// Append a space so the next dictation does not run into this one.
+    pub trailing_space: bool,
+    /// Allow context capture to select and copy the line before the caret with Shift+Home
+    /// and Ctrl+C when the app's text is not readable through accessibility. Off unless set,
+    /// since terminals take Ctrl+C as an interrupt.
+    pub copy_context: bool,
+}
+
+impl AppRule {
+    /// A rule matches when every matcher it sets matches; a rule without matchers never does.
+    pub fn matches(&self, app: &ForegroundApp) -> bool {
+        if self.process_name.is_none() && self.window_class.is_none() {
+            return false;
+        }
+        let process_matches = self.process_name.as_deref().is_none_or(|name| {
+            executable_stem(name).eq_ignore_ascii_case(executable_stem(&app.process_name))
+        });
+        let class_matches = self
+            .window_class
+            .as_deref()
+            .is_none_or(|class| class.eq_ignore_ascii_case(&app.window_class));
+        process_matches && class_matches
+    }
+}
+
+/// The first rule that matches `app`, in the order the user listed them.
+pub fn matching<'a>(rules: &'a [AppRule], app: &ForegroundApp) -> Option<&'a AppRule> {
+    rules.iter().find(|rule| rule.matches(app))
+}
+
+fn executable_stem(name: &str) -> &str {
+    let name = name.trim();
+    match name.len().checked_sub(4) {
+        Some(split)
+            if name.is_char_boundary(split) && name[split..].eq_ignore_ascii_case(".exe") =>
+        {
+            &name[..split]
+        }
+        _ => name,
+    }
+}
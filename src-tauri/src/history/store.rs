@@ -0,0 +1,91 @@
+use std::fs::OpenOptions;
+use std::io::Write;
+use std::path::PathBuf;
+
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+use tauri::{AppHandle, Manager};
+use tokio::sync::Mutex;
+
+use crate::core::merge::Disagreement;
+
+const HISTORY_FILE: &str = "air-keys-history.jsonl";
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SecondOpinionRecord {
+    pub primary_provider: String,
+    pub secondary_provider: String,
+    pub primary_text: String,
+    pub secondary_text: String,
+    pub disagreements: Vec<Disagreement>,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct HistoryEntry {
+    /// RFC 3339 timestamp.
+    pub timestamp: String,
+    pub provider: String,
+    pub text: String,
+    pub second_opinion: Option<SecondOpinionRecord>,
+}
+
+/// Dictations kept for later review, one JSON object per line in the app's data directory.
+/// Only features the user opts into write here.
+pub struct HistoryStore {
+    file_path: PathBuf,
+    lock: Mutex<()>,
+}
+
+impl HistoryStore {
+    pub fn new(app_handle: &AppHandle) -> Result<Self> {
+        let app_data = app_handle
+            .path()
+            .app_local_data_dir()
+            .context("could not resolve local data directory")?;
+        std::fs::create_dir_all(&app_data).context("could not create local data directory")?;
+        Ok(Self {
+            file_path: app_data.join(HISTORY_FILE),
+            lock: Mutex::new(()),
+        })
+    }
+
+    pub async fn append(&self, entry: &HistoryEntry) -> Result<()> {
+        let line = serde_json::to_string(entry).context("could not serialise history entry")?;
+        let _guard = self.lock.lock().await;
+        let mut file = OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(&self.file_path)
+            .context("could not open history file")?;
+        writeln!(file, "{line}").context("could not write history file")?;
+        Ok(())
+    }
+
+    /// Most recent entries first. Lines that no longer parse are skipped.
+    pub async fn list(&self, limit: usize) -> Result<Vec<HistoryEntry>> {
+        let _guard = self.lock.lock().await;
+        let contents = match std::fs::read_to_string(&self.file_path) {
+            Ok(contents) => contents,
+            Err(err) if err.kind() == std::io::ErrorKind::NotFound => return Ok(Vec::new()),
+            Err(err) => return Err(err).context("could not read history file"),
+        };
+        Ok(contents
+            .lines()
+            .rev()
+            .filter_map(|line| serde_json::from_str(line).ok())
+            .take(limit)
+            .collect())
+    }
+
+    pub async fn clear(&self) -> Result<()> {
+        let _guard = self.lock.lock().await;
+        match std::fs::remove_file(&self.file_path) {
+            Err(err) if err.kind() != std::io::ErrorKind::NotFound => {
+                Err(err).context("could not delete history file")
+            }
+            _ => Ok(()),
+        }
+    }
+}
@@ -13,10 +13,10 @@ mod platform {
     use anyhow::Result;
     use once_cell::sync::Lazy;
     use windows::Win32::Foundation::{HINSTANCE, LPARAM, LRESULT, WPARAM};
-    use windows::Win32::UI::Input::KeyboardAndMouse::{VK_LMENU, VK_MENU, VK_RMENU};
+    use windows::Win32::UI::Input::KeyboardAndMouse::{VK_LMENU, VK_MENU, VK_OEM_3, VK_RMENU};
     use windows::Win32::UI::WindowsAndMessaging::{
-        CallNextHookEx, DispatchMessageW, GetMessageW, KBDLLHOOKSTRUCT, SetWindowsHookExW,
-        TranslateMessage, UnhookWindowsHookEx, HC_ACTION, MSG, WH_KEYBOARD_LL, WM_KEYDOWN,
+        CallNextHookEx, DispatchMessageW, GetMessageW, SetWindowsHookExW, TranslateMessage,
+        UnhookWindowsHookEx, HC_ACTION, KBDLLHOOKSTRUCT, MSG, WH_KEYBOARD_LL, WM_KEYDOWN,
         WM_SYSKEYDOWN,
     };
 
@@ -26,14 +26,21 @@ mod platform {
 
     /// Minimum hold duration to trigger "hold Alt to cancel" (ms).
     const HOLD_CANCEL_MS: u64 = 400;
+    /// Alt + this key (backtick on US layouts) cycles through alternatives for the last paste.
+    const ALTERNATIVE_KEY: u32 = VK_OEM_3.0 as u32;
 
     struct TapState {
         last_alt_up: Option<Instant>,
+        /// Whether `last_alt_up` was the right Alt key; two right Alt taps start command mode
+        /// when the setting is on.
+        last_alt_up_right: bool,
         last_alt_down: Option<Instant>,
         alt_is_down: bool,
         hold_seq: u64,
         hold_consumed: bool,
         saw_non_alt: bool,
+        /// Whether the last Alt+` key down was kept from the focused app, so its key up is too.
+        alternative_key_swallowed: bool,
         threshold: Duration,
         hold_cancel: Duration,
         orchestrator: Arc<DictationOrchestrator>,
@@ -44,6 +51,7 @@ mod platform {
         w_param: WPARAM,
         l_param: LPARAM,
     ) -> LRESULT {
+        let mut swallow = false;
         if code == HC_ACTION as i32 {
             let event = *(l_param.0 as *const KBDLLHOOKSTRUCT);
             let message = w_param.0 as u32;
@@ -96,6 +104,7 @@ mod platform {
                             }
                         } else {
                             // Alt key up (WM_KEYUP or WM_SYSKEYUP)
+                            let is_right = event.vkCode == VK_RMENU.0 as u32;
                             state.alt_is_down = false;
                             state.last_alt_down = None;
 
@@ -107,32 +116,65 @@ mod platform {
                                 if let Some(previous) = state.last_alt_up {
                                     if now.duration_since(previous) <= state.threshold {
                                         let orchestrator = state.orchestrator.clone();
+                                        let command = is_right && state.last_alt_up_right;
                                         tauri::async_runtime::spawn(async move {
-                                            if let Err(err) =
+                                            let handled = if command {
+                                                orchestrator.handle_command_double_tap().await
+                                            } else {
                                                 orchestrator.handle_alt_double_tap().await
-                                            {
-                                                log::error!("alt double tap handling failed: {err:#}");
+                                            };
+                                            if let Err(err) = handled {
+                                                log::error!(
+                                                    "alt double tap handling failed: {err:#}"
+                                                );
                                             }
                                         });
                                         state.last_alt_up = None;
                                         state.saw_non_alt = false;
                                     } else {
                                         state.last_alt_up = Some(now);
+                                        state.last_alt_up_right = is_right;
                                     }
                                 } else {
                                     state.last_alt_up = Some(now);
+                                    state.last_alt_up_right = is_right;
                                 }
                             } else {
                                 state.last_alt_up = Some(now);
+                                state.last_alt_up_right = is_right;
                                 state.saw_non_alt = false;
                             }
                         }
                     } else {
                         state.saw_non_alt = true;
+                        if event.vkCode == ALTERNATIVE_KEY {
+                            let is_key_down = message == WM_KEYDOWN || message == WM_SYSKEYDOWN;
+                            // Alt+` stays with the focused app unless there is a paste to
+                            // swap. The key up follows its key down, whether Alt is still held
+                            // or not.
+                            if is_key_down {
+                                state.alternative_key_swallowed =
+                                    state.alt_is_down && state.orchestrator.has_alternatives();
+                                if state.alternative_key_swallowed {
+                                    let orchestrator = state.orchestrator.clone();
+                                    tauri::async_runtime::spawn(async move {
+                                        if let Err(err) = orchestrator.cycle_alternative().await {
+                                            log::error!("alternative picker failed: {err:#}");
+                                        }
+                                    });
+                                }
+                                swallow = state.alternative_key_swallowed;
+                            } else {
+                                swallow = std::mem::take(&mut state.alternative_key_swallowed);
+                            }
+                        }
                     }
                 }
             }
         }
+        if swallow {
+            return LRESULT(1);
+        }
         unsafe { CallNextHookEx(None, code, w_param, l_param) }
     }
 
@@ -143,11 +185,13 @@ mod platform {
         if let Ok(mut guard) = TAP_SINK.lock() {
             *guard = Some(TapState {
                 last_alt_up: None,
+                last_alt_up_right: false,
                 last_alt_down: None,
                 alt_is_down: false,
                 hold_seq: 0,
                 hold_consumed: false,
                 saw_non_alt: false,
+                alternative_key_swallowed: false,
                 threshold: Duration::from_millis(threshold_ms),
                 hold_cancel: Duration::from_millis(HOLD_CANCEL_MS),
                 orchestrator,
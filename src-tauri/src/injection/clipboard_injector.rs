@@ -1,12 +1,34 @@
 use std::time::Instant;
 
-use anyhow::Result;
+use anyhow::{bail, Result};
 use arboard::Clipboard;
 use enigo::{Direction, Enigo, Key, Keyboard, Settings};
+use serde::{Deserialize, Serialize};
 use tokio::time::{sleep, Duration};
 use tracing::instrument;
+use unicode_segmentation::UnicodeSegmentation;
+
+use crate::injection::modifiers;
 
 const PASTE_SETTLE_DELAY_MS: u64 = 120;
+/// How long to wait for the focused application to answer Ctrl+C, polled in steps.
+const COPY_TIMEOUT_MS: u64 = 400;
+const COPY_POLL_MS: u64 = 20;
+/// How long to wait for the user to let go of Shift, Ctrl, Alt or Super before sending keys.
+const MODIFIER_RELEASE_TIMEOUT_MS: u64 = 1_000;
+
+/// How text reaches the focused application.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum InjectionMethod {
+    /// Clipboard plus Ctrl+V.
+    #[default]
+    Paste,
+    /// Clipboard plus Ctrl+Shift+V, which most terminals use for paste.
+    ShiftPaste,
+    /// Simulated key presses, for applications that block or mangle paste.
+    Type,
+}
 
 pub struct ClipboardInjector;
 
@@ -16,8 +38,17 @@ impl ClipboardInjector {
     }
 
     #[instrument(skip(self, transcript), fields(transcript_len = transcript.len()))]
-    pub async fn inject_text(&self, transcript: &str) -> Result<()> {
+    pub async fn inject_text(&self, transcript: &str, method: InjectionMethod) -> Result<()> {
         let start = Instant::now();
+        if method == InjectionMethod::Type {
+            Enigo::new(&Settings::default())?.text(transcript)?;
+            log::info!(
+                "typed injection completed in {}ms",
+                start.elapsed().as_millis()
+            );
+            return Ok(());
+        }
+
         let mut clipboard = Clipboard::new()?;
         let cached_text = clipboard.get_text().ok();
 
@@ -25,25 +56,131 @@ impl ClipboardInjector {
 
         let mut enigo = Enigo::new(&Settings::default())?;
         enigo.key(Key::Control, Direction::Press)?;
+        if method == InjectionMethod::ShiftPaste {
+            enigo.key(Key::Shift, Direction::Press)?;
+        }
         enigo.key(Key::Unicode('v'), Direction::Click)?;
+        if method == InjectionMethod::ShiftPaste {
+            enigo.key(Key::Shift, Direction::Release)?;
+        }
         enigo.key(Key::Control, Direction::Release)?;
 
         // Give the target application a moment to read clipboard contents before restoring.
         sleep(Duration::from_millis(PASTE_SETTLE_DELAY_MS)).await;
 
-        match cached_text {
-            Some(text) => {
-                let _ = clipboard.set_text(text);
-            }
-            None => {
-                let _ = clipboard.clear();
-            }
-        }
-        
+        restore_clipboard(&mut clipboard, cached_text);
+
         log::info!(
             "clipboard injection completed in {}ms",
             start.elapsed().as_millis()
         );
         Ok(())
     }
+
+    /// Copies the focused application's selection with Ctrl+C and puts the clipboard back as it
+    /// was. Returns `None` when nothing is selected or the application did not copy in time.
+    pub async fn copy_selection(&self) -> Result<Option<String>> {
+        let start = Instant::now();
+        let mut clipboard = Clipboard::new()?;
+        let cached_text = clipboard.get_text().ok();
+        clipboard.clear()?;
+
+        let copy = || -> Result<()> {
+            let mut enigo = Enigo::new(&Settings::default())?;
+            enigo.key(Key::Control, Direction::Press)?;
+            enigo.key(Key::Unicode('c'), Direction::Click)?;
+            enigo.key(Key::Control, Direction::Release)?;
+            Ok(())
+        };
+        if let Err(err) = copy() {
+            restore_clipboard(&mut clipboard, cached_text);
+            return Err(err);
+        }
+
+        let mut selection = None;
+        while selection.is_none() && start.elapsed() < Duration::from_millis(COPY_TIMEOUT_MS) {
+            sleep(Duration::from_millis(COPY_POLL_MS)).await;
+            selection = clipboard.get_text().ok().filter(|text| !text.is_empty());
+        }
+        restore_clipboard(&mut clipboard, cached_text);
+
+        log::info!(
+            "selection copy completed in {}ms selection_len={}",
+            start.elapsed().as_millis(),
+            selection.as_ref().map_or(0, String::len)
+        );
+        Ok(selection)
+    }
+
+    /// Copies the current line up to the caret by selecting it with Shift+Home, then collapses
+    /// the selection back to the caret with Right. For applications whose text is not readable
+    /// through the accessibility api; it briefly flashes a selection. Waits for the user to
+    /// let go of modifier keys first, and gives up if they stay down.
+    pub async fn copy_before_caret(&self) -> Result<Option<String>> {
+        wait_for_modifiers_released().await?;
+        let mut enigo = Enigo::new(&Settings::default())?;
+        enigo.key(Key::Shift, Direction::Press)?;
+        let selected = enigo.key(Key::Home, Direction::Click);
+        enigo.key(Key::Shift, Direction::Release)?;
+        selected?;
+
+        let copied = self.copy_selection().await;
+        // Always collapse: a clipboard owner too slow to answer within the timeout looks like
+        // an empty selection, and a line left selected would be replaced by the next paste.
+        if let Err(err) = enigo.key(Key::RightArrow, Direction::Click) {
+            log::warn!("could not collapse the selection before the caret: {err}");
+        }
+        copied
+    }
+
+    /// Selects the `previous` text just left of the caret and pastes `replacement` over it.
+    /// Only reliable right after `previous` was injected, before the caret moves. Shift+Left
+    /// steps over one grapheme cluster, so emoji sequences and combining accents count once.
+    /// Multi-line text is refused: editors may have auto-indented it, so its length on screen
+    /// is unknown.
+    pub async fn replace_previous(
+        &self,
+        previous: &str,
+        replacement: &str,
+        method: InjectionMethod,
+    ) -> Result<()> {
+        if is_multiline(previous) {
+            bail!("cannot reselect text that spans several lines");
+        }
+        {
+            let mut enigo = Enigo::new(&Settings::default())?;
+            enigo.key(Key::Shift, Direction::Press)?;
+            for _ in previous.graphemes(true) {
+                enigo.key(Key::LeftArrow, Direction::Click)?;
+            }
+            enigo.key(Key::Shift, Direction::Release)?;
+        }
+        self.inject_text(replacement, method).await
+    }
+}
+
+async fn wait_for_modifiers_released() -> Result<()> {
+    let start = Instant::now();
+    while modifiers::any_held()? {
+        if start.elapsed() >= Duration::from_millis(MODIFIER_RELEASE_TIMEOUT_MS) {
+            bail!("modifier keys are still held");
+        }
+        sleep(Duration::from_millis(COPY_POLL_MS)).await;
+    }
+    Ok(())
+}
+
+pub fn is_multiline(text: &str) -> bool {
+    text.contains(['\n', '\r'])
+}
+
+fn restore_clipboard(clipboard: &mut Clipboard, cached_text: Option<String>) {
+    match cached_text {
+        Some(text) => {
+            let _ = clipboard.set_text(text);
+        }
+        None => {
+            let _ = clipboard.clear();
+        }
+    }
 }
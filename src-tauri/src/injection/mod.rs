@@ -1 +1,2 @@
 pub mod clipboard_injector;
+pub mod modifiers;
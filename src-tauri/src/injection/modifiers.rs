@@ -0,0 +1,48 @@
+use anyhow::Result;
+
+#[cfg(target_os = "windows")]
+mod platform {
+    use anyhow::Result;
+    use windows::Win32::UI::Input::KeyboardAndMouse::{
+        GetAsyncKeyState, VK_CONTROL, VK_LWIN, VK_MENU, VK_RWIN, VK_SHIFT,
+    };
+
+    pub fn any_held() -> Result<bool> {
+        Ok([VK_SHIFT, VK_CONTROL, VK_MENU, VK_LWIN, VK_RWIN]
+            .iter()
+            .any(|key| unsafe { GetAsyncKeyState(i32::from(key.0)) } < 0))
+    }
+}
+
+#[cfg(target_os = "linux")]
+mod platform {
+    use anyhow::{Context, Result};
+    use x11rb::connection::Connection;
+    use x11rb::protocol::xproto::{ConnectionExt, KeyButMask};
+
+    /// Reads the modifier mask the X server reports with the pointer position.
+    pub fn any_held() -> Result<bool> {
+        let (conn, screen) = x11rb::connect(None).context("could not connect to X server")?;
+        let root = conn.setup().roots[screen].root;
+        let mask = conn.query_pointer(root)?.reply()?.mask;
+        Ok(mask.intersects(
+            KeyButMask::SHIFT | KeyButMask::CONTROL | KeyButMask::MOD1 | KeyButMask::MOD4,
+        ))
+    }
+}
+
+#[cfg(not(any(target_os = "windows", target_os = "linux")))]
+mod platform {
+    use anyhow::{bail, Result};
+
+    pub fn any_held() -> Result<bool> {
+        bail!("modifier key state is not available on this platform")
+    }
+}
+
+/// Whether Shift, Ctrl, Alt or the Windows/Super key is physically down. Synthetic key
+/// presses sent meanwhile combine with them, so Shift+Home under a held Alt is a different
+/// shortcut.
+pub fn any_held() -> Result<bool> {
+    platform::any_held()
+}
@@ -1,30 +1,53 @@
 mod audio;
 mod core;
+mod focus;
+mod history;
 mod hotkey;
 mod injection;
+mod network;
 mod processors;
 mod settings;
+mod tray;
+mod usage;
 
 use std::path::PathBuf;
 use std::sync::Arc;
 
 use core::orchestrator::DictationOrchestrator;
+use core::review::submit_review_decision;
+use history::store::HistoryStore;
 use hotkey::win32_alt_hook::start_alt_double_tap_listener;
-use processors::deepgram::DeepgramProcessor;
-use processors::gemini::GeminiCleaner;
+use network::http_client::HttpClientFactory;
+use processors::registry::ProviderRegistry;
 use settings::commands::{
-    clear_deepgram_api_key, clear_gemini_api_key, get_launch_on_startup_enabled,
-    get_logging_enabled, get_processing_enabled, has_deepgram_api_key, has_gemini_api_key,
-    save_deepgram_api_key, save_gemini_api_key, set_launch_on_startup_enabled,
-    set_logging_enabled, set_processing_enabled, SettingsState,
+    clear_deepgram_api_key, clear_gemini_api_key, clear_history, clear_openai_compatible_api_key,
+    clear_proxy_password, delete_profile, export_replacements, get_active_profile,
+    get_active_providers, get_app_rules, get_cleanup_pipeline, get_command_mode_enabled,
+    get_context_capture_enabled, get_hallucination_blocklist, get_http_policy, get_language,
+    get_last_foreground_app, get_launch_on_startup_enabled, get_logging_enabled,
+    get_processing_enabled, get_provider_settings, get_proxy_settings, get_replacements,
+    get_review_enabled, get_second_opinion, get_spoken_punctuation, get_transcription_fallbacks,
+    get_usage_quota, get_usage_summary, has_deepgram_api_key, has_gemini_api_key,
+    has_openai_compatible_api_key, has_proxy_password, import_replacements, list_history,
+    list_languages, list_profiles, list_providers, save_deepgram_api_key, save_gemini_api_key,
+    save_openai_compatible_api_key, save_profile, save_proxy_password, set_active_profile,
+    set_app_rules, set_cleanup_pipeline, set_cleanup_provider, set_command_mode_enabled,
+    set_context_capture_enabled, set_hallucination_blocklist, set_http_policy, set_language,
+    set_launch_on_startup_enabled, set_logging_enabled, set_processing_enabled,
+    set_provider_settings, set_proxy_settings, set_replacements, set_review_enabled,
+    set_second_opinion, set_spoken_punctuation, set_transcription_fallbacks,
+    set_transcription_provider, set_usage_quota, SettingsState,
 };
-use settings::stronghold_store::StrongholdStore;
+use settings::stronghold_store::{SecureKeyStore, StrongholdStore};
 use tauri::image::Image;
 use tauri::menu::MenuBuilder;
 use tauri::tray::TrayIconBuilder;
 use tauri::Manager;
 use tauri_plugin_autostart::MacosLauncher;
 use tauri_plugin_log::{Target, TargetKind};
+use tray::language_menu::{self, LanguageMenu};
+use tray::profile_menu::{self, ProfileMenu};
+use usage::store::UsageStore;
 
 const TRAY_ID: &str = "air_keys_tray";
 const MENU_SETTINGS: &str = "settings";
@@ -76,23 +99,47 @@ pub fn run() {
                 }
                 app_handle.plugin(builder.build())?;
             }
-            let processor = Arc::new(DeepgramProcessor::new(key_store.clone()));
-            let cleaner = Arc::new(GeminiCleaner::new(key_store.clone()));
+            let history = Arc::new(HistoryStore::new(&app_handle)?);
+            let usage = Arc::new(UsageStore::new(&app_handle)?);
+            let http = Arc::new(HttpClientFactory::new(key_store.clone()));
+            let registry = Arc::new(ProviderRegistry::with_builtin_providers(
+                key_store.clone(),
+                http.clone(),
+                usage.clone(),
+            ));
             let orchestrator = Arc::new(DictationOrchestrator::new(
                 app_handle.clone(),
-                processor,
-                cleaner,
+                registry.clone(),
                 key_store.clone(),
+                history.clone(),
+                usage.clone(),
             )?);
-            app.manage(SettingsState::new(key_store.clone()));
+            app.manage(SettingsState::new(
+                key_store.clone(),
+                registry,
+                http,
+                history,
+                usage,
+            ));
             app.manage(orchestrator.clone());
 
+            let (language_menu, language_submenu) =
+                LanguageMenu::build(&app_handle, &key_store.read_language_blocking())?;
+            app.manage(language_menu);
+            let (profiles, active_profile) = key_store.read_profiles_blocking();
+            let (profile_menu, profile_submenu) =
+                ProfileMenu::build(&app_handle, &profiles, &active_profile)?;
+            app.manage(profile_menu);
+
             let menu = MenuBuilder::new(app)
                 .text(MENU_SETTINGS, "Settings")
+                .item(&language_submenu)
+                .item(&profile_submenu)
                 .separator()
                 .text(MENU_QUIT, "Quit")
                 .build()?;
 
+            let menu_key_store: Arc<dyn SecureKeyStore> = key_store.clone();
             let icon_bytes = include_bytes!("../icons/icon.ico");
             let icon = Image::from_bytes(icon_bytes).map_err(|e| {
                 anyhow::anyhow!("failed to load tray icon: {}", e)
@@ -112,7 +159,12 @@ pub fn run() {
                     MENU_QUIT => {
                         app_handle.exit(0);
                     }
-                    _ => {}
+                    id => {
+                        if !language_menu::handle_menu_event(app_handle, menu_key_store.clone(), id)
+                        {
+                            profile_menu::handle_menu_event(app_handle, menu_key_store.clone(), id);
+                        }
+                    }
                 })
                 .build(app)?;
 
@@ -133,12 +185,65 @@ pub fn run() {
             save_gemini_api_key,
             clear_gemini_api_key,
             has_gemini_api_key,
+            save_openai_compatible_api_key,
+            clear_openai_compatible_api_key,
+            has_openai_compatible_api_key,
             get_processing_enabled,
             set_processing_enabled,
             get_logging_enabled,
             set_logging_enabled,
             get_launch_on_startup_enabled,
-            set_launch_on_startup_enabled
+            set_launch_on_startup_enabled,
+            list_providers,
+            get_active_providers,
+            set_transcription_provider,
+            get_transcription_fallbacks,
+            set_transcription_fallbacks,
+            set_cleanup_provider,
+            get_provider_settings,
+            set_provider_settings,
+            get_http_policy,
+            set_http_policy,
+            get_proxy_settings,
+            set_proxy_settings,
+            save_proxy_password,
+            clear_proxy_password,
+            has_proxy_password,
+            list_languages,
+            get_language,
+            set_language,
+            get_review_enabled,
+            set_review_enabled,
+            submit_review_decision,
+            get_second_opinion,
+            set_second_opinion,
+            list_history,
+            clear_history,
+            get_hallucination_blocklist,
+            set_hallucination_blocklist,
+            get_usage_summary,
+            get_usage_quota,
+            set_usage_quota,
+            list_profiles,
+            save_profile,
+            delete_profile,
+            get_active_profile,
+            set_active_profile,
+            get_app_rules,
+            set_app_rules,
+            get_last_foreground_app,
+            get_spoken_punctuation,
+            set_spoken_punctuation,
+            get_cleanup_pipeline,
+            set_cleanup_pipeline,
+            get_replacements,
+            set_replacements,
+            export_replacements,
+            import_replacements,
+            get_context_capture_enabled,
+            set_context_capture_enabled,
+            get_command_mode_enabled,
+            set_command_mode_enabled
         ])
         .run(tauri::generate_context!())
         .expect("error while running air keys application");
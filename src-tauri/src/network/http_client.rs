@@ -0,0 +1,401 @@
+use std::sync::Arc;
+use std::time::Duration;
+
+use anyhow::{Context, Result};
+use chrono::{DateTime, Utc};
+use reqwest::header::RETRY_AFTER;
+use reqwest::{Method, RequestBuilder, Response, StatusCode};
+use serde::{Deserialize, Serialize};
+use tokio::sync::Mutex;
+
+use crate::network::proxy::ProxySettings;
+use crate::settings::stronghold_store::SecureKeyStore;
+
+/// The longest `Retry-After` we are willing to sit through before giving up on a 429.
+const MAX_RETRY_AFTER: Duration = Duration::from_secs(30);
+
+/// Timeouts and retry behaviour shared by every outbound provider call.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct HttpPolicy {
+    pub connect_timeout_ms: u64,
+    pub request_timeout_ms: u64,
+    pub max_retries: u32,
+    pub initial_backoff_ms: u64,
+    pub max_backoff_ms: u64,
+}
+
+impl Default for HttpPolicy {
+    fn default() -> Self {
+        Self {
+            connect_timeout_ms: 5_000,
+            request_timeout_ms: 120_000,
+            max_retries: 2,
+            initial_backoff_ms: 500,
+            max_backoff_ms: 8_000,
+        }
+    }
+}
+
+impl HttpPolicy {
+    /// Full-jitter exponential backoff: a random delay up to `initial * 2^attempt`, capped.
+    fn backoff(&self, attempt: u32) -> Duration {
+        let ceiling = self
+            .initial_backoff_ms
+            .saturating_mul(1u64 << attempt.min(16))
+            .min(self.max_backoff_ms);
+        Duration::from_millis(rand::random_range(0..=ceiling))
+    }
+}
+
+/// Builds the shared reqwest client from the stored policy and proxy settings, rebuilding it
+/// only when either changes.
+pub struct HttpClientFactory {
+    key_store: Arc<dyn SecureKeyStore>,
+    cached: Mutex<Option<HttpClient>>,
+}
+
+impl HttpClientFactory {
+    pub fn new(key_store: Arc<dyn SecureKeyStore>) -> Self {
+        Self {
+            key_store,
+            cached: Mutex::new(None),
+        }
+    }
+
+    pub async fn client(&self) -> Result<HttpClient> {
+        let policy = self.key_store.read_http_policy().await?;
+        let proxy = self.key_store.read_proxy_settings().await?;
+        let proxy_password = self.key_store.read_proxy_password().await?;
+        let mut cached = self.cached.lock().await;
+        if let Some(client) = cached.as_ref() {
+            if client.policy == policy
+                && client.proxy == proxy
+                && client.proxy_password == proxy_password
+            {
+                return Ok(client.clone());
+            }
+        }
+
+        let client = HttpClient::new(policy, proxy, proxy_password)?;
+        *cached = Some(client.clone());
+        Ok(client)
+    }
+}
+
+#[derive(Clone)]
+pub struct HttpClient {
+    client: reqwest::Client,
+    policy: HttpPolicy,
+    proxy: ProxySettings,
+    proxy_password: Option<String>,
+}
+
+impl HttpClient {
+    fn new(
+        policy: HttpPolicy,
+        proxy: ProxySettings,
+        proxy_password: Option<String>,
+    ) -> Result<Self> {
+        let builder = reqwest::Client::builder()
+            .connect_timeout(Duration::from_millis(policy.connect_timeout_ms))
+            .timeout(Duration::from_millis(policy.request_timeout_ms));
+        let client = proxy
+            .apply(builder, proxy_password.as_deref())?
+            .build()
+            .context("could not build http client")?;
+        Ok(Self {
+            client,
+            policy,
+            proxy,
+            proxy_password,
+        })
+    }
+
+    /// Sends the request built by `build`, retrying with backoff. Connection failures and 429
+    /// responses never reached the provider's handler, so every request retries them. Timeouts
+    /// and 502/503/504 only retry for idempotent methods: an upload or completion that timed
+    /// out may still have run, and must not be sent (and billed) twice. `build` is called once
+    /// per attempt so bodies can be replayed.
+    pub async fn send<F>(&self, build: F) -> reqwest::Result<Response>
+    where
+        F: Fn(&reqwest::Client) -> RequestBuilder,
+    {
+        let mut attempt = 0;
+        loop {
+            let request = build(&self.client).build()?;
+            let idempotent = is_idempotent(request.method());
+            let retries_left = attempt < self.policy.max_retries;
+            let delay = match self.client.execute(request).await {
+                Ok(response)
+                    if retries_left && is_retryable_status(response.status(), idempotent) =>
+                {
+                    match retry_after(&response) {
+                        Some(delay) if delay > MAX_RETRY_AFTER => return Ok(response),
+                        Some(delay) => delay,
+                        None => self.policy.backoff(attempt),
+                    }
+                }
+                Err(err)
+                    if retries_left && (err.is_connect() || (idempotent && err.is_timeout())) =>
+                {
+                    log::warn!("http request failed, retrying: {err}");
+                    self.policy.backoff(attempt)
+                }
+                result => return result,
+            };
+            attempt += 1;
+            log::info!(
+                "retrying http request attempt={} delay={}ms",
+                attempt,
+                delay.as_millis()
+            );
+            tokio::time::sleep(delay).await;
+        }
+    }
+}
+
+fn is_idempotent(method: &Method) -> bool {
+    matches!(
+        *method,
+        Method::GET | Method::HEAD | Method::PUT | Method::DELETE | Method::OPTIONS
+    )
+}
+
+fn is_retryable_status(status: StatusCode, idempotent: bool) -> bool {
+    match status {
+        StatusCode::TOO_MANY_REQUESTS => true,
+        StatusCode::BAD_GATEWAY | StatusCode::SERVICE_UNAVAILABLE | StatusCode::GATEWAY_TIMEOUT => {
+            idempotent
+        }
+        _ => false,
+    }
+}
+
+/// Parses `Retry-After` as either delay-seconds or an HTTP date.
+pub fn retry_after(response: &Response) -> Option<Duration> {
+    let value = response.headers().get(RETRY_AFTER)?.to_str().ok()?.trim();
+    if let Ok(seconds) = value.parse::<u64>() {
+        return Some(Duration::from_secs(seconds));
+    }
+    let retry_at = DateTime::parse_from_rfc2822(value)
+        .ok()?
+        .with_timezone(&Utc);
+    Some((retry_at - Utc::now()).to_std().unwrap_or(Duration::ZERO))
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::{Read, Write};
+    use std::net::{SocketAddr, TcpListener, TcpStream};
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::thread;
+    use std::time::Instant;
+
+    use socket2::{Domain, Socket, Type};
+
+    use super::*;
+
+    /// A canned response, sent after `delay`.
+    #[derive(Clone)]
+    struct Reply {
+        delay: Duration,
+        head: &'static str,
+    }
+
+    const OK: Reply = reply("200 OK");
+    const UNAVAILABLE: Reply = reply("503 Service Unavailable");
+
+    const fn reply(head: &'static str) -> Reply {
+        Reply {
+            delay: Duration::ZERO,
+            head,
+        }
+    }
+
+    /// Answers the n-th connection with the n-th reply (the last one repeats) and counts the
+    /// requests it received.
+    struct MockServer {
+        url: String,
+        requests: Arc<AtomicUsize>,
+    }
+
+    impl MockServer {
+        fn start(replies: Vec<Reply>) -> Self {
+            let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+            let url = format!("http://{}/", listener.local_addr().unwrap());
+            let requests = Arc::new(AtomicUsize::new(0));
+            let counter = requests.clone();
+            thread::spawn(move || {
+                for stream in listener.incoming() {
+                    let index = counter.fetch_add(1, Ordering::SeqCst);
+                    let reply = replies[index.min(replies.len() - 1)].clone();
+                    thread::spawn(move || serve(stream.unwrap(), reply));
+                }
+            });
+            Self { url, requests }
+        }
+
+        fn requests(&self) -> usize {
+            self.requests.load(Ordering::SeqCst)
+        }
+    }
+
+    fn serve(mut stream: TcpStream, reply: Reply) {
+        let mut request = Vec::new();
+        let mut buf = [0u8; 1024];
+        while !request.windows(4).any(|window| window == b"\r\n\r\n") {
+            match stream.read(&mut buf) {
+                Ok(0) | Err(_) => return,
+                Ok(read) => request.extend_from_slice(&buf[..read]),
+            }
+        }
+        thread::sleep(reply.delay);
+        let response = format!(
+            "HTTP/1.1 {}\r\ncontent-length: 0\r\nconnection: close\r\n\r\n",
+            reply.head
+        );
+        let _ = stream.write_all(response.as_bytes());
+    }
+
+    fn client(request_timeout_ms: u64) -> HttpClient {
+        let policy = HttpPolicy {
+            connect_timeout_ms: 200,
+            request_timeout_ms,
+            max_retries: 2,
+            initial_backoff_ms: 1,
+            max_backoff_ms: 5,
+        };
+        HttpClient::new(policy, ProxySettings::default(), None).unwrap()
+    }
+
+    #[tokio::test]
+    async fn retries_server_errors_up_to_the_limit_for_idempotent_requests() {
+        let server = MockServer::start(vec![UNAVAILABLE]);
+        let response = client(2_000)
+            .send(|client| client.get(&server.url))
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::SERVICE_UNAVAILABLE);
+        assert_eq!(server.requests(), 3);
+    }
+
+    #[tokio::test]
+    async fn recovers_when_a_retry_succeeds() {
+        let server = MockServer::start(vec![UNAVAILABLE, OK]);
+        let response = client(2_000)
+            .send(|client| client.get(&server.url))
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+        assert_eq!(server.requests(), 2);
+    }
+
+    #[tokio::test]
+    async fn does_not_retry_server_errors_for_posts() {
+        let server = MockServer::start(vec![UNAVAILABLE, OK]);
+        let response = client(2_000)
+            .send(|client| client.post(&server.url).body("audio"))
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::SERVICE_UNAVAILABLE);
+        assert_eq!(server.requests(), 1);
+    }
+
+    #[tokio::test]
+    async fn retries_timed_out_gets_but_not_posts() {
+        let slow = Reply {
+            delay: Duration::from_millis(500),
+            ..OK
+        };
+        let server = MockServer::start(vec![slow]);
+        let client = client(100);
+
+        let err = client
+            .send(|client| client.get(&server.url))
+            .await
+            .unwrap_err();
+        assert!(err.is_timeout());
+        assert_eq!(server.requests(), 3);
+
+        let err = client
+            .send(|client| client.post(&server.url).body("audio"))
+            .await
+            .unwrap_err();
+        assert!(err.is_timeout());
+        assert_eq!(server.requests(), 4);
+    }
+
+    #[tokio::test]
+    async fn waits_for_retry_after_on_429_even_for_posts() {
+        let server = MockServer::start(vec![reply("429 Too Many Requests\r\nretry-after: 1"), OK]);
+        let start = Instant::now();
+        let response = client(2_000)
+            .send(|client| client.post(&server.url).body("audio"))
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+        assert_eq!(server.requests(), 2);
+        assert!(start.elapsed() >= Duration::from_secs(1));
+    }
+
+    #[tokio::test]
+    async fn gives_up_when_retry_after_exceeds_the_cap() {
+        let server = MockServer::start(vec![reply("429 Too Many Requests\r\nretry-after: 31"), OK]);
+        let start = Instant::now();
+        let response = client(2_000)
+            .send(|client| client.get(&server.url))
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::TOO_MANY_REQUESTS);
+        assert_eq!(server.requests(), 1);
+        assert!(start.elapsed() < MAX_RETRY_AFTER);
+    }
+
+    #[tokio::test]
+    async fn reports_refused_connections_after_retrying() {
+        let url = {
+            let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+            format!("http://{}/", listener.local_addr().unwrap())
+        };
+        let err = client(2_000)
+            .send(|client| client.post(&url).body("audio"))
+            .await
+            .unwrap_err();
+        assert!(err.is_connect());
+    }
+
+    #[tokio::test]
+    async fn retries_connect_timeouts() {
+        // A listener that never accepts and whose backlog is full leaves new connects hanging.
+        let socket = Socket::new(Domain::IPV4, Type::STREAM, None).unwrap();
+        socket
+            .bind(&"127.0.0.1:0".parse::<SocketAddr>().unwrap().into())
+            .unwrap();
+        socket.listen(0).unwrap();
+        let addr = socket.local_addr().unwrap().as_socket().unwrap();
+        let mut queued = Vec::new();
+        while let Ok(stream) = TcpStream::connect_timeout(&addr, Duration::from_millis(100)) {
+            queued.push(stream);
+        }
+
+        let start = Instant::now();
+        let err = client(2_000)
+            .send(|client| client.post(format!("http://{addr}/")).body("audio"))
+            .await
+            .unwrap_err();
+        assert!(err.is_connect());
+        // The first attempt and both retries each waited out the 200ms connect timeout.
+        assert!(start.elapsed() >= Duration::from_millis(600));
+        assert!(start.elapsed() < Duration::from_secs(2));
+    }
+
+    #[test]
+    fn backoff_stays_within_the_exponential_ceiling() {
+        let policy = HttpPolicy::default();
+        for attempt in 0..20 {
+            let ceiling = (policy.initial_backoff_ms << attempt.min(16)).min(policy.max_backoff_ms);
+            assert!(policy.backoff(attempt) <= Duration::from_millis(ceiling));
+        }
+    }
+}
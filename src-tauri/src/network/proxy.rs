@@ -0,0 +1,53 @@
+use anyhow::{Context, Result};
+use reqwest::{Certificate, ClientBuilder, NoProxy, Proxy};
+use serde::{Deserialize, Serialize};
+
+/// Outbound proxy and extra trust roots, for networks behind a TLS-intercepting proxy. The
+/// proxy password is a secret and lives in the key store next to the API keys.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ProxySettings {
+    /// `http://` or `https://` proxy used for every provider call. `None` means direct, or
+    /// whatever the `HTTPS_PROXY` environment variables say.
+    pub url: Option<String>,
+    pub username: Option<String>,
+    /// Comma-separated hosts, domains and CIDR ranges that bypass the proxy, as in `NO_PROXY`.
+    #[serde(default)]
+    pub no_proxy: String,
+    /// PEM bundle trusted in addition to the system roots.
+    pub ca_certificates_pem: Option<String>,
+}
+
+impl ProxySettings {
+    pub fn certificates(&self) -> Result<Vec<Certificate>> {
+        match self.ca_certificates_pem.as_deref().map(str::trim) {
+            Some(pem) if !pem.is_empty() => Certificate::from_pem_bundle(pem.as_bytes())
+                .context("could not read trusted certificates"),
+            _ => Ok(Vec::new()),
+        }
+    }
+
+    pub fn proxy(&self, password: Option<&str>) -> Result<Option<Proxy>> {
+        let Some(url) = self
+            .url
+            .as_deref()
+            .map(str::trim)
+            .filter(|url| !url.is_empty())
+        else {
+            return Ok(None);
+        };
+        let mut proxy = Proxy::all(url).context("invalid proxy url")?;
+        if let Some(username) = self.username.as_deref().filter(|name| !name.is_empty()) {
+            proxy = proxy.basic_auth(username, password.unwrap_or_default());
+        }
+        Ok(Some(proxy.no_proxy(NoProxy::from_string(&self.no_proxy))))
+    }
+
+    pub fn apply(&self, builder: ClientBuilder, password: Option<&str>) -> Result<ClientBuilder> {
+        let mut builder = builder.tls_certs_merge(self.certificates()?);
+        if let Some(proxy) = self.proxy(password)? {
+            builder = builder.proxy(proxy);
+        }
+        Ok(builder)
+    }
+}
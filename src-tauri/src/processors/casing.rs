@@ -0,0 +1,83 @@
+use async_trait::async_trait;
+
+use crate::core::audio_processor::{AudioProcessorError, TranscriptCleaner};
+use crate::core::profile::CleanupProfile;
+use crate::core::transcript::Transcript;
+
+/// Capitalises the first word of each sentence and line, plus the English pronoun "I" and its
+/// contractions. Everything else is left as the earlier stages produced it, and code mode
+/// profiles are left alone entirely.
+pub struct CasingCleaner;
+
+impl CasingCleaner {
+    pub fn new() -> Self {
+        Self
+    }
+}
+
+/// `context` is the text before the caret; a dictation that continues an unfinished sentence
+/// keeps its first word as it is.
+pub fn fix_casing(text: &str, language: Option<&str>, context: Option<&str>) -> String {
+    let english = language.is_none_or(|language| language.starts_with("en"));
+    let mut out = String::with_capacity(text.len());
+    let mut sentence_start = context.is_none_or(|context| {
+        let context = context.trim_end_matches([' ', '\t']);
+        context.is_empty() || context.ends_with('\n') || ends_sentence(context)
+    });
+    for piece in text.split_inclusive(char::is_whitespace) {
+        let word = piece.trim_end();
+        if sentence_start || (english && is_english_i(word)) {
+            out.push_str(&capitalize(piece));
+        } else {
+            out.push_str(piece);
+        }
+        // Bullets and opening quotes on their own do not end or start a sentence.
+        if word.chars().any(char::is_alphanumeric) {
+            sentence_start = ends_sentence(word);
+        }
+        if piece.ends_with('\n') {
+            sentence_start = true;
+        }
+    }
+    out
+}
+
+fn ends_sentence(word: &str) -> bool {
+    word.trim_end_matches(['"', '\'', ')', '»', '”', '“'])
+        .ends_with(['.', '?', '!'])
+}
+
+/// "i", "i'm", "i've", "i'd", "i'll" with any trailing punctuation.
+fn is_english_i(word: &str) -> bool {
+    let word = word.trim_end_matches(|c: char| !c.is_alphanumeric());
+    matches!(word, "i" | "i'm" | "i've" | "i'd" | "i'll")
+}
+
+/// Uppercases the first letter, skipping leading quotes, brackets and list markers.
+fn capitalize(piece: &str) -> String {
+    match piece.char_indices().find(|(_, c)| c.is_alphanumeric()) {
+        Some((index, first)) if first.is_lowercase() => {
+            let rest = &piece[index + first.len_utf8()..];
+            format!("{}{}{rest}", &piece[..index], first.to_uppercase())
+        }
+        _ => piece.to_string(),
+    }
+}
+
+#[async_trait]
+impl TranscriptCleaner for CasingCleaner {
+    async fn clean(
+        &self,
+        transcript: &Transcript,
+        profile: &CleanupProfile,
+    ) -> Result<String, AudioProcessorError> {
+        if profile.code_mode {
+            return Ok(transcript.text.clone());
+        }
+        Ok(fix_casing(
+            &transcript.text,
+            transcript.language.as_deref(),
+            transcript.context.as_deref(),
+        ))
+    }
+}
@@ -0,0 +1,237 @@
+use async_trait::async_trait;
+
+use crate::core::audio_processor::{AudioProcessorError, TranscriptCleaner};
+use crate::core::profile::CleanupProfile;
+use crate::core::transcript::Transcript;
+use crate::processors::spoken_punctuation::{normalize, Grammar, Renderer, Spacing, SpokenCommand};
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum IdentifierCase {
+    Camel,
+    Pascal,
+    Snake,
+    Kebab,
+    Constant,
+}
+
+const CASING_COMMANDS: [(&str, IdentifierCase); 6] = [
+    ("camel case", IdentifierCase::Camel),
+    ("pascal case", IdentifierCase::Pascal),
+    ("snake case", IdentifierCase::Snake),
+    ("kebab case", IdentifierCase::Kebab),
+    ("constant case", IdentifierCase::Constant),
+    ("screaming snake case", IdentifierCase::Constant),
+];
+
+impl IdentifierCase {
+    /// Joins `words` into one identifier, e.g. `["user", "id"]` into `userId` for camel case.
+    fn join(self, words: &[String]) -> String {
+        let parts: Vec<&str> = words
+            .iter()
+            .flat_map(|word| word.split(|c: char| !c.is_alphanumeric()))
+            .filter(|part| !part.is_empty())
+            .collect();
+        let title = |part: &str| {
+            let mut chars = part.chars();
+            match chars.next() {
+                Some(first) => first.to_uppercase().chain(chars).collect::<String>(),
+                None => String::new(),
+            }
+        };
+        match self {
+            IdentifierCase::Camel => match parts.split_first() {
+                Some((first, rest)) => rest.iter().fold(first.to_string(), |mut out, part| {
+                    out.push_str(&title(part));
+                    out
+                }),
+                None => String::new(),
+            },
+            IdentifierCase::Pascal => parts.iter().map(|part| title(part)).collect(),
+            IdentifierCase::Snake => parts.join("_"),
+            IdentifierCase::Kebab => parts.join("-"),
+            IdentifierCase::Constant => parts.join("_").to_uppercase(),
+        }
+    }
+}
+
+fn symbol(phrase: &str, text: &str, spacing: Spacing) -> SpokenCommand {
+    SpokenCommand {
+        phrase: phrase.to_string(),
+        text: text.to_string(),
+        spacing,
+        capitalize_next: false,
+    }
+}
+
+/// Spoken names for operators and punctuation while dictating code.
+fn code_symbols() -> Vec<SpokenCommand> {
+    use Spacing::{AttachLeft, AttachRight, Break, Word};
+
+    vec![
+        symbol("open paren", "(", Break),
+        symbol("close paren", ")", AttachLeft),
+        symbol("open bracket", "[", Break),
+        symbol("close bracket", "]", AttachLeft),
+        symbol("open brace", "{", Word),
+        symbol("close brace", "}", Word),
+        symbol("less than", "<", Word),
+        symbol("greater than", ">", Word),
+        symbol("less than or equal", "<=", Word),
+        symbol("greater than or equal", ">=", Word),
+        symbol("equals", "=", Word),
+        symbol("equals equals", "==", Word),
+        symbol("triple equals", "===", Word),
+        symbol("not equals", "!=", Word),
+        symbol("plus", "+", Word),
+        symbol("plus equals", "+=", Word),
+        symbol("minus", "-", Word),
+        symbol("minus equals", "-=", Word),
+        symbol("times", "*", Word),
+        symbol("divided by", "/", Word),
+        symbol("modulo", "%", Word),
+        symbol("arrow", "->", Word),
+        symbol("fat arrow", "=>", Word),
+        symbol("and and", "&&", Word),
+        symbol("or or", "||", Word),
+        symbol("pipe", "|", Word),
+        symbol("ampersand", "&", AttachRight),
+        symbol("bang", "!", AttachRight),
+        symbol("question mark", "?", AttachLeft),
+        symbol("dot", ".", Break),
+        symbol("comma", ",", AttachLeft),
+        symbol("colon", ":", AttachLeft),
+        symbol("double colon", "::", Break),
+        symbol("semicolon", ";", AttachLeft),
+        symbol("underscore", "_", Break),
+        symbol("slash", "/", Break),
+        symbol("backslash", "\\", Break),
+        symbol("hash", "#", AttachRight),
+        symbol("at sign", "@", AttachRight),
+        symbol("dollar sign", "$", AttachRight),
+        symbol("open quote", "\"", AttachRight),
+        symbol("close quote", "\"", AttachLeft),
+        symbol("open single quote", "'", AttachRight),
+        symbol("close single quote", "'", AttachLeft),
+        symbol("backtick", "`", Break),
+        symbol("new line", "\n", Break),
+        symbol("tab", "\t", Break),
+    ]
+}
+
+fn casing_at(words: &[String]) -> Option<(usize, IdentifierCase)> {
+    CASING_COMMANDS
+        .iter()
+        .map(|(phrase, case)| (phrase.split(' ').collect::<Vec<_>>(), *case))
+        .filter(|(phrase, _)| {
+            phrase.len() <= words.len() && phrase.iter().zip(words).all(|(a, b)| a == b)
+        })
+        .max_by_key(|(phrase, _)| phrase.len())
+        .map(|(phrase, case)| (phrase.len(), case))
+}
+
+/// Renders code dictation: "camel case user id" becomes `userId`, "equals equals" becomes
+/// `==`. A casing command takes every following word up to the next symbol or casing command.
+pub fn render_code(text: &str) -> String {
+    let symbols = code_symbols();
+    let grammar = Grammar::new(&symbols);
+    let tokens: Vec<&str> = text.split_whitespace().collect();
+    let normalized: Vec<String> = tokens.iter().map(|token| normalize(token)).collect();
+    let is_command = |index: usize| {
+        grammar.longest_match(&normalized[index..]).is_some()
+            || casing_at(&normalized[index..]).is_some()
+    };
+
+    let mut renderer = Renderer::default();
+    let mut index = 0;
+    while index < tokens.len() {
+        if let Some((length, case)) = casing_at(&normalized[index..]) {
+            index += length;
+            let start = index;
+            while index < tokens.len() && !is_command(index) {
+                index += 1;
+            }
+            if start < index {
+                renderer.word(&case.join(&normalized[start..index]));
+            }
+        } else if let Some((length, command)) = grammar.longest_match(&normalized[index..]) {
+            renderer.command(command);
+            index += length;
+        } else {
+            // Sentence punctuation the provider added is noise in code.
+            renderer.word(tokens[index].trim_end_matches(['.', ',', '?', '!']));
+            index += 1;
+        }
+    }
+    renderer.finish()
+}
+
+/// Turns identifier casing commands and spoken symbol names into code for profiles with code
+/// mode on. Other profiles pass through unchanged.
+pub struct CodeModeCleaner;
+
+impl CodeModeCleaner {
+    pub fn new() -> Self {
+        Self
+    }
+}
+
+#[async_trait]
+impl TranscriptCleaner for CodeModeCleaner {
+    async fn clean(
+        &self,
+        transcript: &Transcript,
+        profile: &CleanupProfile,
+    ) -> Result<String, AudioProcessorError> {
+        if !profile.code_mode {
+            return Ok(transcript.text.clone());
+        }
+        Ok(render_code(&transcript.text))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn casing_commands_join_the_following_words() {
+        assert_eq!(render_code("camel case user id"), "userId");
+        assert_eq!(render_code("snake case max retries"), "max_retries");
+        assert_eq!(render_code("pascal case http client"), "HttpClient");
+        assert_eq!(
+            render_code("screaming snake case max retries"),
+            "MAX_RETRIES"
+        );
+    }
+
+    #[test]
+    fn spoken_symbols_become_operators() {
+        assert_eq!(
+            render_code("assert open paren count equals equals zero close paren"),
+            "assert(count == zero)"
+        );
+        assert_eq!(
+            render_code("fn main open paren close paren arrow result"),
+            "fn main() -> result"
+        );
+    }
+
+    #[test]
+    fn a_casing_run_stops_at_the_next_symbol() {
+        assert_eq!(
+            render_code("camel case retry count equals snake case max retries"),
+            "retryCount = max_retries"
+        );
+    }
+
+    #[test]
+    fn the_longest_symbol_wins() {
+        assert_eq!(render_code("a equals equals b"), "a == b");
+        assert_eq!(render_code("a equals b"), "a = b");
+    }
+
+    #[test]
+    fn provider_punctuation_is_dropped() {
+        assert_eq!(render_code("Return value."), "Return value");
+    }
+}
@@ -1,38 +1,216 @@
 use std::path::Path;
 use std::sync::Arc;
-use std::time::Instant;
+use std::time::{Duration, Instant};
 
 use async_trait::async_trait;
 use reqwest::header::{AUTHORIZATION, CONTENT_TYPE};
+use reqwest::StatusCode;
 use serde::Deserialize;
+use serde_json::json;
+use tokio::sync::Mutex;
 use tracing::instrument;
 
-use crate::core::audio_processor::{AudioProcessor, AudioProcessorError};
-use crate::settings::stronghold_store::SecureKeyStore;
+use crate::audio::analysis;
+use crate::core::audio_processor::{AudioProcessor, AudioProcessorError, ProviderErrorBody};
+use crate::core::language::LanguageSetting;
+use crate::core::transcript::{Transcript, TranscriptWord};
+use crate::network::http_client::{retry_after, HttpClient, HttpClientFactory};
+use crate::processors::registry::{
+    base_url_setting, Credential, ProviderDescriptor, ProviderKind, SettingField, SettingFieldKind,
+    UrlPreset,
+};
+use crate::settings::stronghold_store::{ProviderSettings, SecureKeyStore};
+use crate::usage::store::{UsageRecord, UsageStore};
 
-const DEEPGRAM_ENDPOINT: &str = "https://api.deepgram.com/v1/listen?model=nova-2&smart_format=true&filler_words=false&punctuate=true";
+pub const PROVIDER_ID: &str = "deepgram";
+
+pub const DEFAULT_BASE_URL: &str = "https://api.deepgram.com";
+const EU_BASE_URL: &str = "https://api.eu.deepgram.com";
+const LISTEN_PATH: &str = "/v1/listen";
+const GRANT_PATH: &str = "/v1/auth/grant";
+/// Lifetime requested for access tokens. Short enough that a leaked token is of little use,
+/// long enough that parallel chunk uploads share one.
+const TOKEN_TTL_SECONDS: u64 = 300;
+/// Tokens this close to expiry are replaced before use.
+const TOKEN_REFRESH_MARGIN: Duration = Duration::from_secs(30);
+const DEFAULT_MODEL: &str = "nova-2";
+/// N-best depth; runner-ups feed the review overlay and the alternative picker.
+const ALTERNATIVES: &str = "3";
+
+pub fn descriptor() -> ProviderDescriptor {
+    ProviderDescriptor {
+        id: PROVIDER_ID,
+        name: "Deepgram",
+        kind: ProviderKind::Transcription,
+        credentials: vec![Credential::DeepgramApiKey],
+        settings: vec![
+            SettingField {
+                key: "model",
+                label: "Model",
+                kind: SettingFieldKind::Select {
+                    options: vec!["nova-2", "nova-3"],
+                },
+                default: DEFAULT_MODEL.into(),
+            },
+            SettingField {
+                key: "base_url",
+                label: "API URL",
+                kind: SettingFieldKind::Url {
+                    presets: vec![
+                        UrlPreset {
+                            label: "Global",
+                            url: DEFAULT_BASE_URL,
+                        },
+                        UrlPreset {
+                            label: "EU data residency",
+                            url: EU_BASE_URL,
+                        },
+                    ],
+                },
+                default: DEFAULT_BASE_URL.into(),
+            },
+        ],
+    }
+}
+
+/// The configured API root: a Deepgram region or a self-hosted deployment.
+pub fn base_url(settings: &ProviderSettings) -> String {
+    base_url_setting(settings, "base_url", DEFAULT_BASE_URL)
+}
+
+/// Whether `base_url` is Deepgram's hosted API, in any region, rather than a self-hosted
+/// deployment.
+pub fn is_cloud_host(base_url: &str) -> bool {
+    reqwest::Url::parse(base_url)
+        .ok()
+        .and_then(|url| url.host_str().map(is_cloud_hostname))
+        .unwrap_or(false)
+}
+
+/// Whether the bare `host` name belongs to Deepgram's hosted API.
+pub fn is_cloud_hostname(host: &str) -> bool {
+    let host = host.to_ascii_lowercase();
+    host == "deepgram.com" || host.ends_with(".deepgram.com")
+}
+
+struct CachedToken {
+    /// The key and API root the token was minted for, so changing either invalidates it.
+    api_key: String,
+    base_url: String,
+    /// Full `Authorization` header value.
+    authorization: String,
+    expires_at: Instant,
+}
 
 #[derive(Clone)]
 pub struct DeepgramProcessor {
-    client: reqwest::Client,
+    http: Arc<HttpClientFactory>,
     key_store: Arc<dyn SecureKeyStore>,
+    usage: Arc<UsageStore>,
+    token: Arc<Mutex<Option<CachedToken>>>,
 }
 
 impl DeepgramProcessor {
-    pub fn new(key_store: Arc<dyn SecureKeyStore>) -> Self {
+    pub fn new(
+        key_store: Arc<dyn SecureKeyStore>,
+        http: Arc<HttpClientFactory>,
+        usage: Arc<UsageStore>,
+    ) -> Self {
         Self {
-            client: reqwest::Client::new(),
+            http,
             key_store,
+            usage,
+            token: Arc::new(Mutex::new(None)),
+        }
+    }
+
+    /// Returns the `Authorization` header for listen requests: a short-lived access token for
+    /// `api_key`, minted through the token grant endpoint when none is cached or the cached one
+    /// is about to expire. The long-lived key only ever travels on grant requests, except
+    /// against self-hosted deployments that have no grant endpoint. Deepgram's hosted API
+    /// always has one, so a 404 there is an error rather than a reason to send the key.
+    async fn authorization(
+        &self,
+        http: &HttpClient,
+        base_url: &str,
+        api_key: &str,
+    ) -> Result<String, AudioProcessorError> {
+        let mut cached = self.token.lock().await;
+        if let Some(token) = cached.as_ref().filter(|token| {
+            token.api_key == api_key
+                && token.base_url == base_url
+                && token.expires_at > Instant::now() + TOKEN_REFRESH_MARGIN
+        }) {
+            return Ok(token.authorization.clone());
+        }
+
+        let body = json!({ "ttl_seconds": TOKEN_TTL_SECONDS });
+        let response = http
+            .send(|client| {
+                client
+                    .post(format!("{base_url}{GRANT_PATH}"))
+                    .header(AUTHORIZATION, format!("Token {api_key}"))
+                    .json(&body)
+            })
+            .await
+            .map_err(|err| AudioProcessorError::from_transport(PROVIDER_ID, &err))?;
+        let status = response.status();
+        if status == StatusCode::NOT_FOUND && !is_cloud_host(base_url) {
+            log::info!("deepgram token grant unavailable at {base_url}; using the api key");
+            let authorization = format!("Token {api_key}");
+            *cached = Some(CachedToken {
+                api_key: api_key.to_string(),
+                base_url: base_url.to_string(),
+                authorization: authorization.clone(),
+                expires_at: Instant::now() + Duration::from_secs(TOKEN_TTL_SECONDS),
+            });
+            return Ok(authorization);
         }
+        if !status.is_success() {
+            let retry_after = retry_after(&response);
+            let body = parse_error_body(&response.text().await.unwrap_or_default());
+            return Err(AudioProcessorError::from_status(
+                PROVIDER_ID,
+                status,
+                retry_after,
+                body,
+            ));
+        }
+        let grant: DeepgramGrantResponse = response.json().await.map_err(|err| {
+            AudioProcessorError::Request(format!("invalid deepgram token grant: {err}"))
+        })?;
+
+        let authorization = format!("Bearer {}", grant.access_token);
+        *cached = Some(CachedToken {
+            api_key: api_key.to_string(),
+            base_url: base_url.to_string(),
+            authorization: authorization.clone(),
+            expires_at: Instant::now()
+                + grant
+                    .expires_in
+                    .filter(|seconds| seconds.is_finite() && *seconds > 0.0)
+                    .map(Duration::from_secs_f64)
+                    .unwrap_or(Duration::from_secs(TOKEN_TTL_SECONDS)),
+        });
+        log::info!("minted deepgram access token");
+        Ok(authorization)
+    }
+
+    async fn invalidate_token(&self) {
+        *self.token.lock().await = None;
     }
 }
 
 #[async_trait]
 impl AudioProcessor for DeepgramProcessor {
     #[instrument(skip(self, audio_path))]
-    async fn process_file(&self, audio_path: &Path) -> Result<String, AudioProcessorError> {
+    async fn process_file(
+        &self,
+        audio_path: &Path,
+        language: &LanguageSetting,
+    ) -> Result<Transcript, AudioProcessorError> {
         let total_start = Instant::now();
-        
+
         let api_key = self
             .key_store
             .read_deepgram_key()
@@ -40,46 +218,98 @@ impl AudioProcessor for DeepgramProcessor {
             .map_err(|err| AudioProcessorError::Request(err.to_string()))?
             .ok_or(AudioProcessorError::MissingApiKey)?;
 
+        let settings = self
+            .key_store
+            .read_provider_settings(PROVIDER_ID)
+            .await
+            .map_err(|err| AudioProcessorError::Request(err.to_string()))?;
+        let model = settings
+            .get("model")
+            .and_then(|value| value.as_str())
+            .unwrap_or(DEFAULT_MODEL);
+        let base_url = base_url(&settings);
+        let mut params = vec![
+            ("model", model),
+            ("smart_format", "true"),
+            ("filler_words", "false"),
+            ("punctuate", "true"),
+            ("alternatives", ALTERNATIVES),
+        ];
+        match language {
+            LanguageSetting::Auto => params.push(("detect_language", "true")),
+            LanguageSetting::Multi => params.push(("language", "multi")),
+            LanguageSetting::Forced(code) => params.push(("language", code.as_str())),
+        }
+        let listen_url = format!("{base_url}{LISTEN_PATH}");
+        let endpoint = reqwest::Url::parse_with_params(&listen_url, params)
+            .map_err(|err| AudioProcessorError::Request(format!("invalid deepgram url: {err}")))?;
+
         let read_start = Instant::now();
         let audio_bytes = std::fs::read(audio_path).map_err(|err| {
             AudioProcessorError::Request(format!("could not read audio file: {err}"))
         })?;
         let file_size = audio_bytes.len();
+        let audio_seconds = analysis::duration(audio_path).map_or(0.0, |d| d.as_secs_f64());
         let read_duration = read_start.elapsed();
 
         let api_start = Instant::now();
-        let response = self
-            .client
-            .post(DEEPGRAM_ENDPOINT)
-            .header(AUTHORIZATION, format!("Token {api_key}"))
-            .header(CONTENT_TYPE, "audio/wav")
-            .body(audio_bytes)
-            .send()
+        let http = self
+            .http
+            .client()
             .await
             .map_err(|err| AudioProcessorError::Request(err.to_string()))?;
+        let mut refreshed = false;
+        let response = loop {
+            let authorization = self.authorization(&http, &base_url, &api_key).await?;
+            let response = http
+                .send(|client| {
+                    client
+                        .post(endpoint.clone())
+                        .header(AUTHORIZATION, authorization.as_str())
+                        .header(CONTENT_TYPE, "audio/wav")
+                        .body(audio_bytes.clone())
+                })
+                .await
+                .map_err(|err| AudioProcessorError::from_transport(PROVIDER_ID, &err))?;
+            // A cached token can be revoked or expire early; mint a fresh one once.
+            if response.status() == StatusCode::UNAUTHORIZED && !refreshed {
+                self.invalidate_token().await;
+                refreshed = true;
+                continue;
+            }
+            break response;
+        };
         let api_duration = api_start.elapsed();
 
-        if !response.status().is_success() {
-            return Err(AudioProcessorError::Request(format!(
-                "deepgram returned status {}",
-                response.status()
-            )));
+        let status = response.status();
+        if !status.is_success() {
+            let retry_after = retry_after(&response);
+            let body = parse_error_body(&response.text().await.unwrap_or_default());
+            return Err(AudioProcessorError::from_status(
+                PROVIDER_ID,
+                status,
+                retry_after,
+                body,
+            ));
         }
 
+        self.usage
+            .record(
+                UsageRecord::audio(PROVIDER_ID, Some(model.to_string()), audio_seconds)
+                    .on_host(&base_url),
+            )
+            .await;
+
         let parse_start = Instant::now();
         let payload: DeepgramResponse = response.json().await.map_err(|err| {
             AudioProcessorError::Request(format!("invalid deepgram payload: {err}"))
         })?;
         let parse_duration = parse_start.elapsed();
 
-        let transcript = payload
-            .results
-            .channels
-            .into_iter()
-            .flat_map(|channel| channel.alternatives.into_iter())
-            .map(|alt| alt.transcript)
-            .find(|transcript| !transcript.trim().is_empty())
-            .ok_or(AudioProcessorError::EmptyTranscript)?;
+        let mut transcript = Transcript::try_from(payload)?;
+        if transcript.language.is_none() {
+            transcript.language = language.forced_code().map(str::to_string);
+        }
 
         let total_duration = total_start.elapsed();
         log::info!(
@@ -89,18 +319,144 @@ impl AudioProcessor for DeepgramProcessor {
             api_duration.as_millis(),
             parse_duration.as_millis(),
             file_size,
-            transcript.len()
+            transcript.text.len()
         );
 
         Ok(transcript)
     }
 }
 
+/// Deepgram reports errors either as `err_code`/`err_msg` or as `category`/`message`.
+fn parse_error_body(text: &str) -> ProviderErrorBody {
+    match serde_json::from_str::<DeepgramErrorResponse>(text) {
+        Ok(error) => ProviderErrorBody {
+            code: error.err_code.or(error.category),
+            message: error.err_msg.or(error.message),
+            request_id: error.request_id,
+        },
+        Err(_) => ProviderErrorBody::from_raw(text),
+    }
+}
+
+#[derive(Debug, Deserialize)]
+struct DeepgramGrantResponse {
+    access_token: String,
+    /// Seconds; Deepgram may report a fractional value.
+    expires_in: Option<f64>,
+}
+
+#[derive(Debug, Deserialize)]
+struct DeepgramErrorResponse {
+    err_code: Option<String>,
+    err_msg: Option<String>,
+    category: Option<String>,
+    message: Option<String>,
+    request_id: Option<String>,
+}
+
+impl TryFrom<DeepgramResponse> for Transcript {
+    type Error = AudioProcessorError;
+
+    /// Takes the first channel alternative with any text in it. The remaining alternatives of
+    /// that channel become runner-up transcripts and per-word candidates.
+    fn try_from(payload: DeepgramResponse) -> Result<Self, Self::Error> {
+        let request_id = payload.metadata.and_then(|metadata| metadata.request_id);
+        for channel in payload.results.channels {
+            let mut alternatives = channel.alternatives;
+            let Some(index) = alternatives
+                .iter()
+                .position(|alt| !alt.transcript.trim().is_empty())
+            else {
+                continue;
+            };
+            let primary = alternatives.remove(index);
+            let words = primary
+                .words
+                .into_iter()
+                .map(|word| {
+                    let text = word.punctuated_word.unwrap_or(word.word);
+                    TranscriptWord {
+                        alternatives: overlapping_candidates(
+                            &alternatives,
+                            &text,
+                            word.start,
+                            word.end,
+                        ),
+                        text,
+                        start: word.start,
+                        end: word.end,
+                        confidence: Some(word.confidence),
+                    }
+                })
+                .collect();
+            let mut runner_ups: Vec<String> = Vec::new();
+            for alt in &alternatives {
+                let text = alt.transcript.trim();
+                if !text.is_empty()
+                    && text != primary.transcript.trim()
+                    && !runner_ups.iter().any(|t| t == text)
+                {
+                    runner_ups.push(text.to_string());
+                }
+            }
+            return Ok(Transcript {
+                text: primary.transcript,
+                words,
+                alternatives: runner_ups,
+                // Multilingual responses list languages by share of words, most used first.
+                language: channel
+                    .detected_language
+                    .or_else(|| primary.languages.first().cloned()),
+                provider: PROVIDER_ID.to_string(),
+                request_id,
+                context: None,
+            });
+        }
+        Err(AudioProcessorError::EmptyTranscript)
+    }
+}
+
+/// Words from the other alternatives that overlap `[start, end)` and differ from `text`.
+fn overlapping_candidates(
+    alternatives: &[DeepgramAlternative],
+    text: &str,
+    start: f64,
+    end: f64,
+) -> Vec<String> {
+    let normalize = |value: &str| {
+        value
+            .chars()
+            .filter(|c| c.is_alphanumeric())
+            .flat_map(char::to_lowercase)
+            .collect::<String>()
+    };
+    let own = normalize(text);
+    let mut candidates: Vec<String> = Vec::new();
+    for word in alternatives.iter().flat_map(|alt| alt.words.iter()) {
+        if word.start >= end || word.end <= start {
+            continue;
+        }
+        let candidate = word.punctuated_word.as_deref().unwrap_or(&word.word);
+        let normalized = normalize(candidate);
+        if normalized == own || candidates.iter().any(|c| normalize(c) == normalized) {
+            continue;
+        }
+        candidates.push(candidate.to_string());
+    }
+    candidates
+}
+
 #[derive(Debug, Deserialize)]
 struct DeepgramResponse {
+    metadata: Option<DeepgramMetadata>,
     results: DeepgramResults,
 }
 
+#[derive(Debug, Deserialize)]
+struct DeepgramMetadata {
+    request_id: Option<String>,
+}
+
 #[derive(Debug, Deserialize)]
 struct DeepgramResults {
     channels: Vec<DeepgramChannel>,
@@ -109,9 +465,23 @@ struct DeepgramResults {
 #[derive(Debug, Deserialize)]
 struct DeepgramChannel {
     alternatives: Vec<DeepgramAlternative>,
+    detected_language: Option<String>,
 }
 
 #[derive(Debug, Deserialize)]
 struct DeepgramAlternative {
     transcript: String,
+    #[serde(default)]
+    words: Vec<DeepgramWord>,
+    #[serde(default)]
+    languages: Vec<String>,
+}
+
+#[derive(Debug, Deserialize)]
+struct DeepgramWord {
+    word: String,
+    punctuated_word: Option<String>,
+    start: f64,
+    end: f64,
+    confidence: f64,
 }
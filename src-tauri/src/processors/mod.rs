@@ -1,2 +1,11 @@
+pub mod casing;
+pub mod cleanup_prompt;
+pub mod code_mode;
 pub mod deepgram;
 pub mod gemini;
+pub mod openai_compatible;
+pub mod registry;
+pub mod replacements;
+pub mod spoken_punctuation;
+pub mod translation;
+pub mod whisper_server;
@@ -0,0 +1,259 @@
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use reqwest::header::{AUTHORIZATION, CONTENT_TYPE};
+use serde::Deserialize;
+use serde_json::json;
+
+use crate::core::audio_processor::{AudioProcessorError, ProviderErrorBody, TranscriptCleaner};
+use crate::core::profile::CleanupProfile;
+use crate::core::transcript::Transcript;
+use crate::network::http_client::{retry_after, HttpClientFactory};
+use crate::processors::cleanup_prompt;
+use crate::processors::registry::{
+    base_url_setting, ProviderDescriptor, ProviderKind, SettingField, SettingFieldKind, UrlPreset,
+};
+use crate::settings::stronghold_store::SecureKeyStore;
+use crate::usage::store::{UsageRecord, UsageStore};
+
+pub const PROVIDER_ID: &str = "openai_compatible";
+
+const DEFAULT_BASE_URL: &str = "http://127.0.0.1:11434";
+const DEFAULT_MODEL: &str = "llama3.2";
+const DEFAULT_TEMPERATURE: f64 = 0.0;
+const CHAT_COMPLETIONS_PATH: &str = "/v1/chat/completions";
+const MAX_OUTPUT_TOKENS: u32 = 2048;
+
+pub fn descriptor() -> ProviderDescriptor {
+    ProviderDescriptor {
+        id: PROVIDER_ID,
+        name: "OpenAI-compatible (Ollama, LM Studio, ...)",
+        kind: ProviderKind::Cleanup,
+        // Local servers usually need no key; one is sent only when stored.
+        credentials: Vec::new(),
+        settings: vec![
+            SettingField {
+                key: "base_url",
+                label: "Server URL",
+                kind: SettingFieldKind::Url {
+                    presets: vec![
+                        UrlPreset {
+                            label: "Ollama on this computer",
+                            url: DEFAULT_BASE_URL,
+                        },
+                        UrlPreset {
+                            label: "LM Studio on this computer",
+                            url: "http://127.0.0.1:1234",
+                        },
+                        UrlPreset {
+                            label: "OpenAI",
+                            url: "https://api.openai.com",
+                        },
+                    ],
+                },
+                default: DEFAULT_BASE_URL.into(),
+            },
+            SettingField {
+                key: "model",
+                label: "Model",
+                kind: SettingFieldKind::Text,
+                default: DEFAULT_MODEL.into(),
+            },
+            SettingField {
+                key: "temperature",
+                label: "Temperature",
+                kind: SettingFieldKind::Number { min: 0.0, max: 2.0 },
+                default: DEFAULT_TEMPERATURE.into(),
+            },
+        ],
+    }
+}
+
+/// Cleans transcripts through any server exposing the OpenAI `/v1/chat/completions` endpoint,
+/// with the same profiles and output guard as the Gemini cleaner.
+#[derive(Clone)]
+pub struct OpenAiCompatibleCleaner {
+    http: Arc<HttpClientFactory>,
+    key_store: Arc<dyn SecureKeyStore>,
+    usage: Arc<UsageStore>,
+}
+
+impl OpenAiCompatibleCleaner {
+    pub fn new(
+        key_store: Arc<dyn SecureKeyStore>,
+        http: Arc<HttpClientFactory>,
+        usage: Arc<UsageStore>,
+    ) -> Self {
+        Self {
+            http,
+            key_store,
+            usage,
+        }
+    }
+}
+
+#[async_trait]
+impl TranscriptCleaner for OpenAiCompatibleCleaner {
+    async fn clean(
+        &self,
+        transcript: &Transcript,
+        profile: &CleanupProfile,
+    ) -> Result<String, AudioProcessorError> {
+        let language = transcript.language.as_deref();
+        let context = transcript.context.as_deref();
+        let transcript = transcript.text.as_str();
+        if transcript.trim().is_empty() {
+            return Ok(String::new());
+        }
+
+        let api_key = self
+            .key_store
+            .read_openai_compatible_key()
+            .await
+            .map_err(|err| AudioProcessorError::Request(err.to_string()))?;
+        let settings = self
+            .key_store
+            .read_provider_settings(PROVIDER_ID)
+            .await
+            .map_err(|err| AudioProcessorError::Request(err.to_string()))?;
+        let base_url = base_url_setting(&settings, "base_url", DEFAULT_BASE_URL);
+        let model = profile.model_for(PROVIDER_ID).unwrap_or_else(|| {
+            settings
+                .get("model")
+                .and_then(|value| value.as_str())
+                .map(str::trim)
+                .filter(|model| !model.is_empty())
+                .unwrap_or(DEFAULT_MODEL)
+        });
+        let temperature = profile.temperature.unwrap_or_else(|| {
+            settings
+                .get("temperature")
+                .and_then(|value| value.as_f64())
+                .unwrap_or(DEFAULT_TEMPERATURE)
+        });
+        let endpoint = format!("{base_url}{CHAT_COMPLETIONS_PATH}");
+
+        let body = json!({
+            "model": model,
+            "messages": [
+                { "role": "system", "content": profile.prompt },
+                {
+                    "role": "user",
+                    "content": cleanup_prompt::user_message(transcript, language, context)
+                }
+            ],
+            "temperature": temperature,
+            "max_tokens": MAX_OUTPUT_TOKENS,
+            "stream": false
+        });
+
+        let http = self
+            .http
+            .client()
+            .await
+            .map_err(|err| AudioProcessorError::Request(err.to_string()))?;
+        let response = http
+            .send(|client| {
+                let request = client
+                    .post(&endpoint)
+                    .header(CONTENT_TYPE, "application/json")
+                    .json(&body);
+                match &api_key {
+                    Some(api_key) => request.header(AUTHORIZATION, format!("Bearer {api_key}")),
+                    None => request,
+                }
+            })
+            .await
+            .map_err(|err| AudioProcessorError::from_transport(PROVIDER_ID, &err))?;
+
+        let status = response.status();
+        if !status.is_success() {
+            let retry_after = retry_after(&response);
+            let body = parse_error_body(&response.text().await.unwrap_or_default());
+            return Err(AudioProcessorError::from_status(
+                PROVIDER_ID,
+                status,
+                retry_after,
+                body,
+            ));
+        }
+
+        let payload: ChatCompletionResponse = response.json().await.map_err(|err| {
+            AudioProcessorError::Request(format!("invalid chat completion payload: {err}"))
+        })?;
+        if let Some(usage) = &payload.usage {
+            self.usage
+                .record(
+                    UsageRecord::tokens(
+                        PROVIDER_ID,
+                        Some(model.to_string()),
+                        usage.prompt_tokens,
+                        usage.completion_tokens,
+                    )
+                    .on_host(&base_url),
+                )
+                .await;
+        }
+
+        let cleaned = payload
+            .choices
+            .into_iter()
+            .filter_map(|choice| choice.message.content)
+            .find(|text| !text.trim().is_empty())
+            .ok_or_else(|| {
+                AudioProcessorError::Request(
+                    "chat completion returned an empty transcript".to_string(),
+                )
+            })?;
+
+        Ok(cleanup_prompt::guard_output(transcript, &cleaned))
+    }
+}
+
+/// OpenAI-compatible servers wrap errors as `{"error": {"message", "type", "code"}}`.
+fn parse_error_body(text: &str) -> ProviderErrorBody {
+    match serde_json::from_str::<ChatCompletionErrorResponse>(text) {
+        Ok(response) => ProviderErrorBody {
+            code: response.error.kind,
+            message: response.error.message,
+            request_id: None,
+        },
+        Err(_) => ProviderErrorBody::from_raw(text),
+    }
+}
+
+#[derive(Debug, Deserialize)]
+struct ChatCompletionErrorResponse {
+    error: ChatCompletionError,
+}
+
+#[derive(Debug, Deserialize)]
+struct ChatCompletionError {
+    message: Option<String>,
+    #[serde(rename = "type")]
+    kind: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+struct ChatCompletionResponse {
+    choices: Vec<ChatCompletionChoice>,
+    usage: Option<ChatCompletionUsage>,
+}
+
+#[derive(Debug, Deserialize)]
+struct ChatCompletionChoice {
+    message: ChatCompletionMessage,
+}
+
+#[derive(Debug, Deserialize)]
+struct ChatCompletionMessage {
+    content: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+struct ChatCompletionUsage {
+    #[serde(default)]
+    prompt_tokens: u64,
+    #[serde(default)]
+    completion_tokens: u64,
+}
@@ -0,0 +1,263 @@
+use std::sync::Arc;
+
+use anyhow::{anyhow, Result};
+use serde::Serialize;
+
+use crate::core::audio_processor::{AudioProcessor, TranscriptCleaner};
+use crate::network::http_client::HttpClientFactory;
+use crate::processors::deepgram::{self, DeepgramProcessor};
+use crate::processors::gemini::{self, GeminiCleaner};
+use crate::processors::openai_compatible::{self, OpenAiCompatibleCleaner};
+use crate::processors::whisper_server::{self, WhisperServerProcessor};
+use crate::settings::stronghold_store::{ProviderSettings, SecureKeyStore};
+use crate::usage::store::UsageStore;
+
+pub const DEFAULT_TRANSCRIPTION_PROVIDER: &str = deepgram::PROVIDER_ID;
+pub const DEFAULT_CLEANUP_PROVIDER: &str = gemini::PROVIDER_ID;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ProviderKind {
+    Transcription,
+    Cleanup,
+}
+
+/// Secrets a provider needs before it can be used. Values live in the key store, never in
+/// provider settings.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Credential {
+    DeepgramApiKey,
+    GeminiApiKey,
+}
+
+impl Credential {
+    pub async fn is_present(&self, key_store: &dyn SecureKeyStore) -> Result<bool> {
+        let value = match self {
+            Credential::DeepgramApiKey => key_store.read_deepgram_key().await?,
+            Credential::GeminiApiKey => key_store.read_gemini_key().await?,
+        };
+        Ok(value.is_some())
+    }
+}
+
+#[derive(Clone, Debug, Serialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum SettingFieldKind {
+    Text,
+    Select {
+        options: Vec<&'static str>,
+    },
+    Number {
+        min: f64,
+        max: f64,
+    },
+    /// An http(s) base URL. Presets are offered next to free input, e.g. for data regions.
+    Url {
+        presets: Vec<UrlPreset>,
+    },
+}
+
+#[derive(Clone, Debug, Serialize)]
+pub struct UrlPreset {
+    pub label: &'static str,
+    pub url: &'static str,
+}
+
+#[derive(Clone, Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SettingField {
+    pub key: &'static str,
+    pub label: &'static str,
+    pub kind: SettingFieldKind,
+    pub default: serde_json::Value,
+}
+
+#[derive(Clone, Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ProviderDescriptor {
+    pub id: &'static str,
+    pub name: &'static str,
+    pub kind: ProviderKind,
+    pub credentials: Vec<Credential>,
+    pub settings: Vec<SettingField>,
+}
+
+impl ProviderDescriptor {
+    pub fn field(&self, key: &str) -> Option<&SettingField> {
+        self.settings.iter().find(|field| field.key == key)
+    }
+}
+
+/// Reads a base URL setting, without a trailing slash so paths can be appended.
+pub fn base_url_setting(settings: &ProviderSettings, key: &str, default: &str) -> String {
+    settings
+        .get(key)
+        .and_then(|value| value.as_str())
+        .map(str::trim)
+        .filter(|value| !value.is_empty())
+        .unwrap_or(default)
+        .trim_end_matches('/')
+        .to_string()
+}
+
+struct RegisteredProvider<T: ?Sized> {
+    descriptor: ProviderDescriptor,
+    instance: Arc<T>,
+}
+
+/// Every transcription and cleanup implementation the app knows about. The active ones are
+/// looked up from settings at call time so switching engines needs no restart.
+pub struct ProviderRegistry {
+    processors: Vec<RegisteredProvider<dyn AudioProcessor>>,
+    cleaners: Vec<RegisteredProvider<dyn TranscriptCleaner>>,
+}
+
+impl ProviderRegistry {
+    pub fn new() -> Self {
+        Self {
+            processors: Vec::new(),
+            cleaners: Vec::new(),
+        }
+    }
+
+    pub fn with_builtin_providers(
+        key_store: Arc<dyn SecureKeyStore>,
+        http: Arc<HttpClientFactory>,
+        usage: Arc<UsageStore>,
+    ) -> Self {
+        let mut registry = Self::new();
+        registry.register_processor(
+            deepgram::descriptor(),
+            Arc::new(DeepgramProcessor::new(
+                key_store.clone(),
+                http.clone(),
+                usage.clone(),
+            )),
+        );
+        registry.register_processor(
+            whisper_server::descriptor(),
+            Arc::new(WhisperServerProcessor::new(
+                key_store.clone(),
+                http.clone(),
+                usage.clone(),
+            )),
+        );
+        registry.register_cleaner(
+            gemini::descriptor(),
+            Arc::new(GeminiCleaner::new(
+                key_store.clone(),
+                http.clone(),
+                usage.clone(),
+            )),
+        );
+        registry.register_cleaner(
+            openai_compatible::descriptor(),
+            Arc::new(OpenAiCompatibleCleaner::new(key_store, http, usage)),
+        );
+        registry
+    }
+
+    pub fn register_processor(
+        &mut self,
+        descriptor: ProviderDescriptor,
+        instance: Arc<dyn AudioProcessor>,
+    ) {
+        debug_assert_eq!(descriptor.kind, ProviderKind::Transcription);
+        self.processors.push(RegisteredProvider {
+            descriptor,
+            instance,
+        });
+    }
+
+    pub fn register_cleaner(
+        &mut self,
+        descriptor: ProviderDescriptor,
+        instance: Arc<dyn TranscriptCleaner>,
+    ) {
+        debug_assert_eq!(descriptor.kind, ProviderKind::Cleanup);
+        self.cleaners.push(RegisteredProvider {
+            descriptor,
+            instance,
+        });
+    }
+
+    pub fn descriptors(&self) -> Vec<ProviderDescriptor> {
+        self.processors
+            .iter()
+            .map(|entry| entry.descriptor.clone())
+            .chain(self.cleaners.iter().map(|entry| entry.descriptor.clone()))
+            .collect()
+    }
+
+    pub fn descriptor(&self, id: &str) -> Option<&ProviderDescriptor> {
+        self.processors
+            .iter()
+            .map(|entry| &entry.descriptor)
+            .chain(self.cleaners.iter().map(|entry| &entry.descriptor))
+            .find(|descriptor| descriptor.id == id)
+    }
+
+    pub fn processor(&self, id: &str) -> Option<Arc<dyn AudioProcessor>> {
+        self.processors
+            .iter()
+            .find(|entry| entry.descriptor.id == id)
+            .map(|entry| entry.instance.clone())
+    }
+
+    pub fn cleaner(&self, id: &str) -> Option<Arc<dyn TranscriptCleaner>> {
+        self.cleaners
+            .iter()
+            .find(|entry| entry.descriptor.id == id)
+            .map(|entry| entry.instance.clone())
+    }
+
+    /// Resolves the transcription provider currently selected in settings.
+    pub async fn active_processor(
+        &self,
+        key_store: &dyn SecureKeyStore,
+    ) -> Result<(String, Arc<dyn AudioProcessor>)> {
+        let id = key_store
+            .read_transcription_provider()
+            .await?
+            .unwrap_or_else(|| DEFAULT_TRANSCRIPTION_PROVIDER.to_string());
+        let processor = self
+            .processor(&id)
+            .ok_or_else(|| anyhow!("unknown transcription provider: {id}"))?;
+        Ok((id, processor))
+    }
+
+    /// The active transcription provider followed by the configured fallbacks, in the order they
+    /// should be tried. Unknown or duplicate ids are dropped.
+    pub async fn transcription_chain(
+        &self,
+        key_store: &dyn SecureKeyStore,
+    ) -> Result<Vec<(String, Arc<dyn AudioProcessor>)>> {
+        let mut chain = vec![self.active_processor(key_store).await?];
+        for id in key_store.read_transcription_fallbacks().await? {
+            if chain.iter().any(|(existing, _)| *existing == id) {
+                continue;
+            }
+            match self.processor(&id) {
+                Some(processor) => chain.push((id, processor)),
+                None => log::warn!("ignoring unknown fallback provider: {id}"),
+            }
+        }
+        Ok(chain)
+    }
+
+    /// Resolves the cleanup provider currently selected in settings.
+    pub async fn active_cleaner(
+        &self,
+        key_store: &dyn SecureKeyStore,
+    ) -> Result<(String, Arc<dyn TranscriptCleaner>)> {
+        let id = key_store
+            .read_cleanup_provider()
+            .await?
+            .unwrap_or_else(|| DEFAULT_CLEANUP_PROVIDER.to_string());
+        let cleaner = self
+            .cleaner(&id)
+            .ok_or_else(|| anyhow!("unknown cleanup provider: {id}"))?;
+        Ok((id, cleaner))
+    }
+}
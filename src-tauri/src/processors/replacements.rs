@@ -0,0 +1,387 @@
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use regex::{Captures, Regex, RegexBuilder};
+use serde::{Deserialize, Serialize};
+
+use crate::core::audio_processor::{AudioProcessorError, TranscriptCleaner};
+use crate::core::profile::CleanupProfile;
+use crate::core::transcript::Transcript;
+use crate::settings::stronghold_store::SecureKeyStore;
+
+const CSV_HEADER: [&str; 6] = [
+    "find",
+    "replace",
+    "kind",
+    "wholeWord",
+    "caseSensitive",
+    "preserveCase",
+];
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum MatchKind {
+    #[default]
+    Literal,
+    /// `find` is a regular expression and `replace` may refer to groups as `$1` or `${name}`.
+    Regex,
+}
+
+impl MatchKind {
+    fn as_str(self) -> &'static str {
+        match self {
+            MatchKind::Literal => "literal",
+            MatchKind::Regex => "regex",
+        }
+    }
+}
+
+/// One entry of the personal replacements dictionary, e.g. "get hub" -> "GitHub".
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Replacement {
+    pub find: String,
+    pub replace: String,
+    #[serde(default)]
+    pub kind: MatchKind,
+    /// Only match where `find` starts and ends on a word boundary.
+    #[serde(default = "default_true")]
+    pub whole_word: bool,
+    #[serde(default)]
+    pub case_sensitive: bool,
+    /// Uppercase the replacement when the match was all caps, or its first letter when the
+    /// match was capitalised.
+    #[serde(default)]
+    pub preserve_case: bool,
+}
+
+fn default_true() -> bool {
+    true
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ExchangeFormat {
+    Csv,
+    Json,
+}
+
+/// A replacement with its pattern compiled once per run.
+pub struct CompiledReplacement<'a> {
+    regex: Regex,
+    rule: &'a Replacement,
+}
+
+pub fn compile(rules: &[Replacement]) -> Result<Vec<CompiledReplacement<'_>>, regex::Error> {
+    rules
+        .iter()
+        .map(|rule| {
+            // A boundary next to "+" or "." would never match, e.g. for "c++".
+            let boundary = |c: Option<char>| match c {
+                Some(c) if c.is_alphanumeric() || c == '_' => r"\b",
+                _ => "",
+            };
+            let pattern = match rule.kind {
+                MatchKind::Literal if rule.whole_word => format!(
+                    "{}{}{}",
+                    boundary(rule.find.chars().next()),
+                    regex::escape(&rule.find),
+                    boundary(rule.find.chars().last()),
+                ),
+                MatchKind::Literal => regex::escape(&rule.find),
+                MatchKind::Regex if rule.whole_word => format!(r"\b(?:{})\b", rule.find),
+                MatchKind::Regex => rule.find.clone(),
+            };
+            let regex = RegexBuilder::new(&pattern)
+                .case_insensitive(!rule.case_sensitive)
+                .build()?;
+            Ok(CompiledReplacement { regex, rule })
+        })
+        .collect()
+}
+
+/// Applies every replacement in table order, each to the output of the previous one.
+pub fn apply(text: &str, replacements: &[CompiledReplacement<'_>]) -> String {
+    let mut text = text.to_string();
+    for compiled in replacements {
+        let rule = compiled.rule;
+        text = compiled
+            .regex
+            .replace_all(&text, |caps: &Captures<'_>| {
+                let mut replacement = String::new();
+                match rule.kind {
+                    MatchKind::Literal => replacement.push_str(&rule.replace),
+                    MatchKind::Regex => caps.expand(&rule.replace, &mut replacement),
+                }
+                if rule.preserve_case {
+                    match_case(&caps[0], &replacement)
+                } else {
+                    replacement
+                }
+            })
+            .into_owned();
+    }
+    text
+}
+
+fn match_case(matched: &str, replacement: &str) -> String {
+    let mut letters = matched.chars().filter(|c| c.is_alphabetic()).peekable();
+    let first_upper = letters.peek().is_some_and(|c| c.is_uppercase());
+    if first_upper && letters.clone().count() > 1 && letters.all(char::is_uppercase) {
+        return replacement.to_uppercase();
+    }
+    if !first_upper {
+        return replacement.to_string();
+    }
+    match replacement.char_indices().find(|(_, c)| c.is_alphabetic()) {
+        Some((index, first)) => {
+            let rest = &replacement[index + first.len_utf8()..];
+            format!("{}{}{rest}", &replacement[..index], first.to_uppercase())
+        }
+        None => replacement.to_string(),
+    }
+}
+
+pub fn export(rules: &[Replacement], format: ExchangeFormat) -> Result<String, String> {
+    match format {
+        ExchangeFormat::Json => serde_json::to_string_pretty(rules)
+            .map_err(|err| format!("could not encode replacements: {err}")),
+        ExchangeFormat::Csv => {
+            let mut out = CSV_HEADER.join(",");
+            out.push('\n');
+            for rule in rules {
+                let fields = [
+                    csv_field(&rule.find),
+                    csv_field(&rule.replace),
+                    rule.kind.as_str().to_string(),
+                    rule.whole_word.to_string(),
+                    rule.case_sensitive.to_string(),
+                    rule.preserve_case.to_string(),
+                ];
+                out.push_str(&fields.join(","));
+                out.push('\n');
+            }
+            Ok(out)
+        }
+    }
+}
+
+pub fn import(content: &str, format: ExchangeFormat) -> Result<Vec<Replacement>, String> {
+    match format {
+        ExchangeFormat::Json => serde_json::from_str(content)
+            .map_err(|err| format!("not a replacements JSON file: {err}")),
+        ExchangeFormat::Csv => {
+            let mut rows = parse_csv(content)?.into_iter();
+            let header: Vec<String> = rows.next().unwrap_or_default();
+            let column = |name: &str| header.iter().position(|field| field.trim() == name);
+            let (Some(find), Some(replace)) = (column("find"), column("replace")) else {
+                return Err("CSV needs \"find\" and \"replace\" columns.".to_string());
+            };
+            let flag = |row: &[String], name: &str, default: bool| {
+                column(name)
+                    .and_then(|index| row.get(index))
+                    .map_or(default, |value| value.trim().eq_ignore_ascii_case("true"))
+            };
+            Ok(rows
+                .filter(|row| row.iter().any(|field| !field.is_empty()))
+                .map(|row| {
+                    let kind = match column("kind").and_then(|index| row.get(index)) {
+                        Some(kind) if kind.trim() == "regex" => MatchKind::Regex,
+                        _ => MatchKind::Literal,
+                    };
+                    Replacement {
+                        find: row.get(find).cloned().unwrap_or_default(),
+                        replace: row.get(replace).cloned().unwrap_or_default(),
+                        kind,
+                        whole_word: flag(&row, "wholeWord", true),
+                        case_sensitive: flag(&row, "caseSensitive", false),
+                        preserve_case: flag(&row, "preserveCase", false),
+                    }
+                })
+                .collect())
+        }
+    }
+}
+
+fn csv_field(value: &str) -> String {
+    if value.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", value.replace('"', "\"\""))
+    } else {
+        value.to_string()
+    }
+}
+
+/// RFC 4180 rows: quoted fields may hold commas, doubled quotes and line breaks.
+fn parse_csv(content: &str) -> Result<Vec<Vec<String>>, String> {
+    let mut rows = Vec::new();
+    let mut row = Vec::new();
+    let mut field = String::new();
+    let mut quoted = false;
+    let mut chars = content.trim_start_matches('\u{feff}').chars().peekable();
+    while let Some(c) = chars.next() {
+        match (quoted, c) {
+            (true, '"') if chars.peek() == Some(&'"') => {
+                chars.next();
+                field.push('"');
+            }
+            (true, '"') => quoted = false,
+            (true, c) => field.push(c),
+            (false, '"') if field.is_empty() => quoted = true,
+            (false, ',') => row.push(std::mem::take(&mut field)),
+            (false, '\r') => {}
+            (false, '\n') => {
+                row.push(std::mem::take(&mut field));
+                rows.push(std::mem::take(&mut row));
+            }
+            (false, c) => field.push(c),
+        }
+    }
+    if quoted {
+        return Err("CSV has an unterminated quoted field.".to_string());
+    }
+    if !field.is_empty() || !row.is_empty() {
+        row.push(field);
+        rows.push(row);
+    }
+    Ok(rows)
+}
+
+/// Applies the user's replacements dictionary. Runs locally and gives the same output for the
+/// same input, whichever providers are configured.
+pub struct ReplacementsCleaner {
+    key_store: Arc<dyn SecureKeyStore>,
+}
+
+impl ReplacementsCleaner {
+    pub fn new(key_store: Arc<dyn SecureKeyStore>) -> Self {
+        Self { key_store }
+    }
+}
+
+#[async_trait]
+impl TranscriptCleaner for ReplacementsCleaner {
+    async fn clean(
+        &self,
+        transcript: &Transcript,
+        _profile: &CleanupProfile,
+    ) -> Result<String, AudioProcessorError> {
+        let rules = self
+            .key_store
+            .read_replacements()
+            .await
+            .map_err(|err| AudioProcessorError::Request(err.to_string()))?;
+        let compiled = compile(&rules).map_err(|err| {
+            AudioProcessorError::Request(format!("invalid replacement pattern: {err}"))
+        })?;
+        Ok(apply(&transcript.text, &compiled))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn rule(find: &str, replace: &str) -> Replacement {
+        Replacement {
+            find: find.to_string(),
+            replace: replace.to_string(),
+            kind: MatchKind::Literal,
+            whole_word: true,
+            case_sensitive: false,
+            preserve_case: false,
+        }
+    }
+
+    fn replace_all(text: &str, rules: &[Replacement]) -> String {
+        apply(text, &compile(rules).unwrap())
+    }
+
+    #[test]
+    fn rewrites_the_dictionary_examples() {
+        let rules = [rule("get hub", "GitHub"), rule("cube cuttle", "kubectl")];
+        assert_eq!(
+            replace_all("push to get hub then run cube cuttle apply", &rules),
+            "push to GitHub then run kubectl apply"
+        );
+    }
+
+    #[test]
+    fn whole_words_only_match_on_word_boundaries() {
+        let rules = [rule("cat", "dog")];
+        assert_eq!(replace_all("cat concatenate", &rules), "dog concatenate");
+    }
+
+    #[test]
+    fn literals_ending_in_symbols_still_match_as_whole_words() {
+        let rules = [rule("c++", "C++")];
+        assert_eq!(
+            replace_all("i write c++ daily", &rules),
+            "i write C++ daily"
+        );
+        assert_eq!(replace_all("abc++", &rules), "abc++");
+    }
+
+    #[test]
+    fn regex_replacements_expand_groups() {
+        let rules = [Replacement {
+            kind: MatchKind::Regex,
+            ..rule(r"ticket (\d+)", "#$1")
+        }];
+        assert_eq!(replace_all("see ticket 42", &rules), "see #42");
+    }
+
+    #[test]
+    fn preserve_case_follows_the_matched_text() {
+        let rules = [Replacement {
+            preserve_case: true,
+            ..rule("cube cuttle", "kubectl")
+        }];
+        assert_eq!(replace_all("CUBE CUTTLE", &rules), "KUBECTL");
+        assert_eq!(replace_all("Cube cuttle", &rules), "Kubectl");
+        assert_eq!(replace_all("cube cuttle", &rules), "kubectl");
+    }
+
+    #[test]
+    fn parse_csv_handles_quotes_commas_and_line_breaks() {
+        let rows =
+            parse_csv("find,replace\n\"a, b\",\"say \"\"hi\"\"\"\r\n\"two\nlines\",x\n").unwrap();
+        assert_eq!(
+            rows,
+            vec![
+                vec!["find".to_string(), "replace".to_string()],
+                vec!["a, b".to_string(), "say \"hi\"".to_string()],
+                vec!["two\nlines".to_string(), "x".to_string()],
+            ]
+        );
+    }
+
+    #[test]
+    fn export_then_import_round_trips() {
+        let rules = vec![
+            rule("get hub", "GitHub"),
+            Replacement {
+                kind: MatchKind::Regex,
+                whole_word: false,
+                case_sensitive: true,
+                preserve_case: true,
+                ..rule(r"(\d+), (\d+)", "\"$1\"\n$2")
+            },
+        ];
+        for format in [ExchangeFormat::Csv, ExchangeFormat::Json] {
+            let exported = export(&rules, format).unwrap();
+            assert_eq!(import(&exported, format).unwrap(), rules, "{format:?}");
+        }
+    }
+
+    #[test]
+    fn import_rejects_csv_without_find_and_replace_columns() {
+        assert!(import("from,to\nget hub,GitHub\n", ExchangeFormat::Csv).is_err());
+        assert!(import("", ExchangeFormat::Csv).is_err());
+    }
+
+    #[test]
+    fn import_rejects_an_unterminated_quote() {
+        let csv = "find,replace\n\"get hub,GitHub\n";
+        assert!(import(csv, ExchangeFormat::Csv).is_err());
+    }
+}
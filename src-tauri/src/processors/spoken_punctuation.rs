@@ -0,0 +1,441 @@
+use std::collections::BTreeMap;
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use serde::{Deserialize, Serialize};
+
+use crate::core::audio_processor::{AudioProcessorError, TranscriptCleaner};
+use crate::core::profile::CleanupProfile;
+use crate::core::transcript::Transcript;
+use crate::settings::stronghold_store::SecureKeyStore;
+
+/// Grammar used when the transcript carries no language.
+const DEFAULT_GRAMMAR_LANGUAGE: &str = "en";
+
+/// How a command's text joins the words around it.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Spacing {
+    /// Like a word: spaces on both sides.
+    Word,
+    /// Sticks to the previous word, e.g. "," or a closing quote.
+    AttachLeft,
+    /// Sticks to the next word, e.g. an opening quote.
+    AttachRight,
+    /// No spaces on either side, e.g. a line break.
+    Break,
+    /// Starts a new line unless already at the start of one, e.g. a bullet.
+    LineStart,
+}
+
+/// One spoken phrase and what it turns into.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SpokenCommand {
+    pub phrase: String,
+    pub text: String,
+    pub spacing: Spacing,
+    /// Capitalise the word that follows, e.g. after "period" or "new line".
+    #[serde(default)]
+    pub capitalize_next: bool,
+}
+
+/// Spoken commands keyed by base language code (`en`, `de`, ...).
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SpokenPunctuationSettings {
+    #[serde(default = "default_grammars")]
+    pub grammars: BTreeMap<String, Vec<SpokenCommand>>,
+}
+
+impl Default for SpokenPunctuationSettings {
+    fn default() -> Self {
+        Self {
+            grammars: default_grammars(),
+        }
+    }
+}
+
+impl SpokenPunctuationSettings {
+    /// The grammar for a BCP-47 code such as `en-GB`, matched on its base language.
+    pub fn grammar(&self, language: Option<&str>) -> &[SpokenCommand] {
+        let language = language
+            .and_then(|language| language.split('-').next())
+            .unwrap_or(DEFAULT_GRAMMAR_LANGUAGE)
+            .to_ascii_lowercase();
+        self.grammars
+            .get(&language)
+            .map(Vec::as_slice)
+            .unwrap_or_default()
+    }
+}
+
+fn command(phrase: &str, text: &str, spacing: Spacing, capitalize_next: bool) -> SpokenCommand {
+    SpokenCommand {
+        phrase: phrase.to_string(),
+        text: text.to_string(),
+        spacing,
+        capitalize_next,
+    }
+}
+
+/// Built-in command grammars. Users can edit or replace them per language in settings.
+///
+/// Phrases are replaced wherever they are spoken, so while the stage is on ordinary words such
+/// as "period", "colon", "point" or "punto" always become punctuation too. Users who need them
+/// as words remove those commands from their grammar.
+pub fn default_grammars() -> BTreeMap<String, Vec<SpokenCommand>> {
+    use Spacing::{AttachLeft, AttachRight, Break, LineStart};
+
+    let english = vec![
+        command("comma", ",", AttachLeft, false),
+        command("period", ".", AttachLeft, true),
+        command("full stop", ".", AttachLeft, true),
+        command("question mark", "?", AttachLeft, true),
+        command("exclamation mark", "!", AttachLeft, true),
+        command("exclamation point", "!", AttachLeft, true),
+        command("colon", ":", AttachLeft, false),
+        command("semicolon", ";", AttachLeft, false),
+        command("open quote", "\"", AttachRight, false),
+        command("close quote", "\"", AttachLeft, false),
+        command("end quote", "\"", AttachLeft, false),
+        command("open paren", "(", AttachRight, false),
+        command("close paren", ")", AttachLeft, false),
+        command("new line", "\n", Break, true),
+        command("new paragraph", "\n\n", Break, true),
+        command("bullet point", "- ", LineStart, true),
+    ];
+    let german = vec![
+        command("komma", ",", AttachLeft, false),
+        command("punkt", ".", AttachLeft, true),
+        command("fragezeichen", "?", AttachLeft, true),
+        command("ausrufezeichen", "!", AttachLeft, true),
+        command("doppelpunkt", ":", AttachLeft, false),
+        command("semikolon", ";", AttachLeft, false),
+        command("anführungszeichen auf", "„", AttachRight, false),
+        command("anführungszeichen zu", "“", AttachLeft, false),
+        command("klammer auf", "(", AttachRight, false),
+        command("klammer zu", ")", AttachLeft, false),
+        command("neue zeile", "\n", Break, true),
+        command("neuer absatz", "\n\n", Break, true),
+        command("aufzählungspunkt", "- ", LineStart, true),
+    ];
+    let french = vec![
+        command("virgule", ",", AttachLeft, false),
+        command("point", ".", AttachLeft, true),
+        command("point d'interrogation", "?", AttachLeft, true),
+        command("point d'exclamation", "!", AttachLeft, true),
+        command("deux points", ":", AttachLeft, false),
+        command("point-virgule", ";", AttachLeft, false),
+        command("ouvrir les guillemets", "« ", AttachRight, false),
+        command("fermer les guillemets", " »", AttachLeft, false),
+        command("ouvrir la parenthèse", "(", AttachRight, false),
+        command("fermer la parenthèse", ")", AttachLeft, false),
+        command("à la ligne", "\n", Break, true),
+        command("nouveau paragraphe", "\n\n", Break, true),
+        command("puce", "- ", LineStart, true),
+    ];
+    let spanish = vec![
+        command("coma", ",", AttachLeft, false),
+        command("punto", ".", AttachLeft, true),
+        command("signo de interrogación", "?", AttachLeft, true),
+        command("signo de exclamación", "!", AttachLeft, true),
+        command("dos puntos", ":", AttachLeft, false),
+        command("punto y coma", ";", AttachLeft, false),
+        command("abrir comillas", "\"", AttachRight, false),
+        command("cerrar comillas", "\"", AttachLeft, false),
+        command("abrir paréntesis", "(", AttachRight, false),
+        command("cerrar paréntesis", ")", AttachLeft, false),
+        command("nueva línea", "\n", Break, true),
+        command("nuevo párrafo", "\n\n", Break, true),
+        command("viñeta", "- ", LineStart, true),
+    ];
+    BTreeMap::from([
+        ("de".to_string(), german),
+        ("en".to_string(), english),
+        ("es".to_string(), spanish),
+        ("fr".to_string(), french),
+    ])
+}
+
+/// Lowercases a token and drops punctuation the provider attached to it, so "Comma," and
+/// "comma" both match. That punctuation is lost when the token is a command: "a period."
+/// renders as "a.".
+pub fn normalize(token: &str) -> String {
+    token
+        .trim_matches(|c: char| !c.is_alphanumeric())
+        .to_lowercase()
+}
+
+fn capitalize(word: &str) -> String {
+    let mut chars = word.chars();
+    match chars.next() {
+        Some(first) => first.to_uppercase().chain(chars).collect(),
+        None => String::new(),
+    }
+}
+
+/// Command phrases split into normalised words, ready for matching against a transcript.
+pub struct Grammar<'a> {
+    phrases: Vec<(Vec<String>, &'a SpokenCommand)>,
+}
+
+impl<'a> Grammar<'a> {
+    pub fn new(commands: &'a [SpokenCommand]) -> Self {
+        let phrases = commands
+            .iter()
+            .map(|command| {
+                let words: Vec<String> = command.phrase.split_whitespace().map(normalize).collect();
+                (words, command)
+            })
+            .filter(|(words, _)| !words.is_empty())
+            .collect();
+        Self { phrases }
+    }
+
+    /// The command whose phrase starts `words`, with its length in words. The longest phrase
+    /// wins where several match, so "point d'interrogation" beats "point".
+    pub fn longest_match(&self, words: &[String]) -> Option<(usize, &'a SpokenCommand)> {
+        self.phrases
+            .iter()
+            .filter(|(phrase, _)| words.starts_with(phrase))
+            .max_by_key(|(phrase, _)| phrase.len())
+            .map(|(phrase, command)| (phrase.len(), *command))
+    }
+}
+
+/// Joins words and rendered commands with the spacing each command asks for.
+pub struct Renderer {
+    out: String,
+    /// Whether the next piece joins without a space, as at the start or after a line break.
+    attach_next: bool,
+    capitalize_next: bool,
+}
+
+impl Default for Renderer {
+    fn default() -> Self {
+        Self {
+            out: String::new(),
+            attach_next: true,
+            capitalize_next: false,
+        }
+    }
+}
+
+impl Renderer {
+    pub fn word(&mut self, word: &str) {
+        if !self.attach_next {
+            self.out.push(' ');
+        }
+        if self.capitalize_next {
+            self.out.push_str(&capitalize(word));
+        } else {
+            self.out.push_str(word);
+        }
+        self.attach_next = false;
+        self.capitalize_next = false;
+    }
+
+    pub fn command(&mut self, command: &SpokenCommand) {
+        match command.spacing {
+            Spacing::Word => {
+                if !self.attach_next {
+                    self.out.push(' ');
+                }
+                self.attach_next = false;
+            }
+            Spacing::AttachLeft => self.attach_next = false,
+            Spacing::AttachRight => {
+                if !self.attach_next {
+                    self.out.push(' ');
+                }
+                self.attach_next = true;
+            }
+            Spacing::Break => self.attach_next = true,
+            Spacing::LineStart => {
+                if !self.out.is_empty() && !self.out.ends_with('\n') {
+                    self.out.push('\n');
+                }
+                self.attach_next = true;
+            }
+        }
+        self.out.push_str(&command.text);
+        self.capitalize_next = command.capitalize_next;
+    }
+
+    pub fn finish(self) -> String {
+        self.out
+    }
+}
+
+/// Replaces spoken commands in `text` with their rendering. Everything else is kept as is.
+pub fn render(text: &str, grammar: &[SpokenCommand]) -> String {
+    let grammar = Grammar::new(grammar);
+    let tokens: Vec<&str> = text.split_whitespace().collect();
+    let normalized: Vec<String> = tokens.iter().map(|token| normalize(token)).collect();
+
+    let mut renderer = Renderer::default();
+    let mut index = 0;
+    while index < tokens.len() {
+        match grammar.longest_match(&normalized[index..]) {
+            Some((length, command)) => {
+                renderer.command(command);
+                index += length;
+            }
+            None => {
+                renderer.word(tokens[index]);
+                index += 1;
+            }
+        }
+    }
+    renderer.finish()
+}
+
+/// Renders spoken punctuation and formatting commands with a fixed per-language grammar, so
+/// they work the same with or without an LLM.
+pub struct SpokenPunctuationCleaner {
+    key_store: Arc<dyn SecureKeyStore>,
+}
+
+impl SpokenPunctuationCleaner {
+    pub fn new(key_store: Arc<dyn SecureKeyStore>) -> Self {
+        Self { key_store }
+    }
+}
+
+#[async_trait]
+impl TranscriptCleaner for SpokenPunctuationCleaner {
+    async fn clean(
+        &self,
+        transcript: &Transcript,
+        _profile: &CleanupProfile,
+    ) -> Result<String, AudioProcessorError> {
+        let settings = self
+            .key_store
+            .read_spoken_punctuation()
+            .await
+            .map_err(|err| AudioProcessorError::Request(err.to_string()))?;
+        let grammar = settings.grammar(transcript.language.as_deref());
+        Ok(render(&transcript.text, grammar))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn english(text: &str) -> String {
+        let settings = SpokenPunctuationSettings::default();
+        render(text, settings.grammar(Some("en")))
+    }
+
+    fn words(text: &str) -> Vec<String> {
+        text.split_whitespace().map(normalize).collect()
+    }
+
+    #[test]
+    fn renders_each_spacing() {
+        let grammar = vec![
+            command("and sign", "&", Spacing::Word, false),
+            command("comma", ",", Spacing::AttachLeft, false),
+            command("open quote", "\"", Spacing::AttachRight, false),
+            command("close quote", "\"", Spacing::AttachLeft, false),
+            command("slash", "/", Spacing::Break, false),
+            command("bullet", "- ", Spacing::LineStart, false),
+        ];
+        assert_eq!(render("salt and sign pepper", &grammar), "salt & pepper");
+        assert_eq!(render("and sign more", &grammar), "& more");
+        assert_eq!(render("yes comma no", &grammar), "yes, no");
+        assert_eq!(
+            render("she said open quote hi close quote", &grammar),
+            "she said \"hi\""
+        );
+        assert_eq!(render("open quote hi", &grammar), "\"hi");
+        assert_eq!(render("and slash or", &grammar), "and/or");
+        assert_eq!(
+            render("list bullet eggs bullet milk", &grammar),
+            "list\n- eggs\n- milk"
+        );
+        assert_eq!(render("bullet eggs", &grammar), "- eggs");
+    }
+
+    #[test]
+    fn line_start_does_not_add_a_second_line_break() {
+        assert_eq!(english("list new line bullet point eggs"), "list\n- Eggs");
+    }
+
+    #[test]
+    fn capitalizes_after_sentence_ends_and_line_breaks() {
+        assert_eq!(english("done period next"), "done. Next");
+        assert_eq!(english("really question mark yes"), "really? Yes");
+        assert_eq!(english("one new paragraph two"), "one\n\nTwo");
+        assert_eq!(english("done comma next"), "done, next");
+        assert_eq!(english("done colon next"), "done: next");
+    }
+
+    #[test]
+    fn keeps_words_that_are_not_commands() {
+        assert_eq!(english("Hello there, world."), "Hello there, world.");
+        assert_eq!(english(""), "");
+    }
+
+    #[test]
+    fn matches_commands_regardless_of_case_and_provider_punctuation() {
+        assert_eq!(english("Hello Comma, world"), "Hello, world");
+        assert_eq!(english("Open quote, hi. Close quote."), "\"hi.\"");
+    }
+
+    #[test]
+    fn drops_provider_punctuation_on_command_words() {
+        // Built-in phrases are ordinary words too; they are rewritten either way.
+        assert_eq!(english("a period."), "a.");
+        assert_eq!(english("a long period of time"), "a long. Of time");
+    }
+
+    #[test]
+    fn longest_match_prefers_the_longer_phrase() {
+        let settings = SpokenPunctuationSettings::default();
+        let grammar = Grammar::new(settings.grammar(Some("fr")));
+
+        let (length, command) = grammar
+            .longest_match(&words("point d'interrogation oui"))
+            .unwrap();
+        assert_eq!((length, command.text.as_str()), (2, "?"));
+
+        let (length, command) = grammar.longest_match(&words("point oui")).unwrap();
+        assert_eq!((length, command.text.as_str()), (1, "."));
+
+        assert!(grammar.longest_match(&words("oui point")).is_none());
+        assert!(grammar.longest_match(&[]).is_none());
+    }
+
+    #[test]
+    fn renders_the_longest_phrase_in_context() {
+        let settings = SpokenPunctuationSettings::default();
+        let french = settings.grammar(Some("fr"));
+        assert_eq!(
+            render("vraiment point d'interrogation oui point", french),
+            "vraiment? Oui."
+        );
+        let spanish = settings.grammar(Some("es"));
+        assert_eq!(render("uno punto y coma dos", spanish), "uno; dos");
+    }
+
+    #[test]
+    fn grammar_is_looked_up_by_base_language() {
+        let settings = SpokenPunctuationSettings::default();
+        assert_eq!(
+            settings.grammar(Some("en-GB")),
+            settings.grammar(Some("en"))
+        );
+        assert_eq!(settings.grammar(Some("DE")), settings.grammar(Some("de")));
+        assert_eq!(settings.grammar(None), settings.grammar(Some("en")));
+        assert!(settings.grammar(Some("ja")).is_empty());
+    }
+
+    #[test]
+    fn ignores_commands_with_empty_phrases() {
+        let grammar = vec![command("  ", "!", Spacing::Word, false)];
+        assert_eq!(render("hello there", &grammar), "hello there");
+    }
+}
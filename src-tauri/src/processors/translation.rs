@@ -0,0 +1,68 @@
+use std::sync::Arc;
+
+use async_trait::async_trait;
+
+use crate::core::audio_processor::{AudioProcessorError, TranscriptCleaner};
+use crate::core::profile::CleanupProfile;
+use crate::core::transcript::Transcript;
+use crate::processors::registry::ProviderRegistry;
+use crate::settings::stronghold_store::SecureKeyStore;
+
+/// Translates dictations for profiles that ask for it, through a cleanup provider running the
+/// translation prompt. Other profiles pass through unchanged.
+pub struct TranslationCleaner {
+    key_store: Arc<dyn SecureKeyStore>,
+    registry: Arc<ProviderRegistry>,
+}
+
+impl TranslationCleaner {
+    pub fn new(key_store: Arc<dyn SecureKeyStore>, registry: Arc<ProviderRegistry>) -> Self {
+        Self {
+            key_store,
+            registry,
+        }
+    }
+
+    async fn translator(
+        &self,
+        provider: Option<&str>,
+    ) -> Result<Arc<dyn TranscriptCleaner>, AudioProcessorError> {
+        match provider {
+            Some(id) => self.registry.cleaner(id).ok_or_else(|| {
+                AudioProcessorError::Request(format!("unknown translation provider: {id}"))
+            }),
+            None => self
+                .registry
+                .active_cleaner(self.key_store.as_ref())
+                .await
+                .map(|(_, cleaner)| cleaner)
+                .map_err(|err| AudioProcessorError::Request(format!("{err:#}"))),
+        }
+    }
+}
+
+#[async_trait]
+impl TranscriptCleaner for TranslationCleaner {
+    async fn clean(
+        &self,
+        transcript: &Transcript,
+        profile: &CleanupProfile,
+    ) -> Result<String, AudioProcessorError> {
+        let Some(translation) = &profile.translation else {
+            return Ok(transcript.text.clone());
+        };
+        let translator = self.translator(translation.provider.as_deref()).await?;
+        let translating = CleanupProfile {
+            prompt: translation.prompt(),
+            translation: None,
+            ..profile.clone()
+        };
+        // The cleaners tell the model to keep the detected language; the prompt names the
+        // source language instead.
+        let source = Transcript {
+            language: None,
+            ..transcript.clone()
+        };
+        translator.clean(&source, &translating).await
+    }
+}
@@ -0,0 +1,228 @@
+use std::path::Path;
+use std::sync::Arc;
+use std::time::Instant;
+
+use async_trait::async_trait;
+use reqwest::multipart::{Form, Part};
+use serde::Deserialize;
+use tracing::instrument;
+
+use crate::audio::analysis;
+use crate::core::audio_processor::{AudioProcessor, AudioProcessorError, ProviderErrorBody};
+use crate::core::language::LanguageSetting;
+use crate::core::transcript::{Transcript, TranscriptWord};
+use crate::network::http_client::{retry_after, HttpClientFactory};
+use crate::processors::registry::{
+    base_url_setting, ProviderDescriptor, ProviderKind, SettingField, SettingFieldKind, UrlPreset,
+};
+use crate::settings::stronghold_store::SecureKeyStore;
+use crate::usage::store::{UsageRecord, UsageStore};
+
+pub const PROVIDER_ID: &str = "whisper_server";
+
+const DEFAULT_BASE_URL: &str = "http://127.0.0.1:8080";
+const DEFAULT_MODEL: &str = "whisper-1";
+const TRANSCRIPTIONS_PATH: &str = "/v1/audio/transcriptions";
+
+pub fn descriptor() -> ProviderDescriptor {
+    ProviderDescriptor {
+        id: PROVIDER_ID,
+        name: "Local Whisper server",
+        kind: ProviderKind::Transcription,
+        credentials: Vec::new(),
+        settings: vec![
+            SettingField {
+                key: "base_url",
+                label: "Server URL",
+                kind: SettingFieldKind::Url {
+                    presets: vec![UrlPreset {
+                        label: "This computer",
+                        url: DEFAULT_BASE_URL,
+                    }],
+                },
+                default: DEFAULT_BASE_URL.into(),
+            },
+            SettingField {
+                key: "model",
+                label: "Model",
+                kind: SettingFieldKind::Text,
+                default: DEFAULT_MODEL.into(),
+            },
+        ],
+    }
+}
+
+/// Transcribes against a self-hosted server exposing the OpenAI-compatible
+/// `/v1/audio/transcriptions` endpoint (whisper.cpp, faster-whisper-server, LocalAI, ...).
+#[derive(Clone)]
+pub struct WhisperServerProcessor {
+    http: Arc<HttpClientFactory>,
+    key_store: Arc<dyn SecureKeyStore>,
+    usage: Arc<UsageStore>,
+}
+
+impl WhisperServerProcessor {
+    pub fn new(
+        key_store: Arc<dyn SecureKeyStore>,
+        http: Arc<HttpClientFactory>,
+        usage: Arc<UsageStore>,
+    ) -> Self {
+        Self {
+            http,
+            key_store,
+            usage,
+        }
+    }
+}
+
+#[async_trait]
+impl AudioProcessor for WhisperServerProcessor {
+    #[instrument(skip(self, audio_path))]
+    async fn process_file(
+        &self,
+        audio_path: &Path,
+        language: &LanguageSetting,
+    ) -> Result<Transcript, AudioProcessorError> {
+        let total_start = Instant::now();
+
+        let settings = self
+            .key_store
+            .read_provider_settings(PROVIDER_ID)
+            .await
+            .map_err(|err| AudioProcessorError::Request(err.to_string()))?;
+        let base_url = base_url_setting(&settings, "base_url", DEFAULT_BASE_URL);
+        let model = settings
+            .get("model")
+            .and_then(|value| value.as_str())
+            .unwrap_or(DEFAULT_MODEL)
+            .to_string();
+        let endpoint = format!("{base_url}{TRANSCRIPTIONS_PATH}");
+
+        let audio_bytes = std::fs::read(audio_path).map_err(|err| {
+            AudioProcessorError::Request(format!("could not read audio file: {err}"))
+        })?;
+        let file_size = audio_bytes.len();
+        let audio_seconds = analysis::duration(audio_path).map_or(0.0, |d| d.as_secs_f64());
+
+        let http = self
+            .http
+            .client()
+            .await
+            .map_err(|err| AudioProcessorError::Request(err.to_string()))?;
+        let response = http
+            .send(|client| {
+                let file_part = Part::bytes(audio_bytes.clone())
+                    .file_name("dictation.wav")
+                    .mime_str("audio/wav")
+                    .expect("audio/wav is a valid mime type");
+                let mut form = Form::new()
+                    .part("file", file_part)
+                    .text("model", model.clone())
+                    .text("response_format", "verbose_json")
+                    .text("timestamp_granularities[]", "word");
+                // Whisper detects the language on its own and copes with code-switching, so
+                // only a forced language is passed through.
+                if let LanguageSetting::Forced(code) = language {
+                    form = form.text("language", code.clone());
+                }
+                client.post(&endpoint).multipart(form)
+            })
+            .await
+            .map_err(|err| AudioProcessorError::from_transport(PROVIDER_ID, &err))?;
+
+        let status = response.status();
+        if !status.is_success() {
+            let retry_after = retry_after(&response);
+            let body = parse_error_body(&response.text().await.unwrap_or_default());
+            return Err(AudioProcessorError::from_status(
+                PROVIDER_ID,
+                status,
+                retry_after,
+                body,
+            ));
+        }
+
+        self.usage
+            .record(
+                UsageRecord::audio(PROVIDER_ID, Some(model.clone()), audio_seconds)
+                    .on_host(&base_url),
+            )
+            .await;
+
+        let payload: WhisperServerResponse = response.json().await.map_err(|err| {
+            AudioProcessorError::Request(format!("invalid whisper server payload: {err}"))
+        })?;
+        let text = payload.text.trim().to_string();
+        if text.is_empty() {
+            return Err(AudioProcessorError::EmptyTranscript);
+        }
+        let transcript = Transcript {
+            text,
+            words: payload
+                .words
+                .into_iter()
+                .map(|word| TranscriptWord {
+                    text: word.word.trim().to_string(),
+                    start: word.start,
+                    end: word.end,
+                    confidence: word.probability,
+                    alternatives: Vec::new(),
+                })
+                .collect(),
+            alternatives: Vec::new(),
+            language: payload.language,
+            provider: PROVIDER_ID.to_string(),
+            request_id: None,
+            context: None,
+        };
+
+        log::info!(
+            "whisper server transcription completed total={}ms file_size={}B transcript_len={}",
+            total_start.elapsed().as_millis(),
+            file_size,
+            transcript.text.len()
+        );
+
+        Ok(transcript)
+    }
+}
+
+/// OpenAI-compatible servers wrap errors as `{"error": {"message", "type", "code"}}`.
+fn parse_error_body(text: &str) -> ProviderErrorBody {
+    match serde_json::from_str::<WhisperServerErrorResponse>(text) {
+        Ok(response) => ProviderErrorBody {
+            code: response.error.kind,
+            message: response.error.message,
+            request_id: None,
+        },
+        Err(_) => ProviderErrorBody::from_raw(text),
+    }
+}
+
+#[derive(Debug, Deserialize)]
+struct WhisperServerErrorResponse {
+    error: WhisperServerError,
+}
+
+#[derive(Debug, Deserialize)]
+struct WhisperServerError {
+    message: Option<String>,
+    #[serde(rename = "type")]
+    kind: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+struct WhisperServerResponse {
+    text: String,
+    language: Option<String>,
+    #[serde(default)]
+    words: Vec<WhisperServerWord>,
+}
+
+#[derive(Debug, Deserialize)]
+struct WhisperServerWord {
+    word: String,
+    start: f64,
+    end: f64,
+    probability: Option<f64>,
+}
@@ -1,28 +1,102 @@
 use std::sync::Arc;
 
+use serde::Serialize;
 use tauri::{AppHandle, State};
 use tauri_plugin_autostart::ManagerExt;
 
-use super::stronghold_store::SecureKeyStore;
-use super::validation::{validate_deepgram_key, validate_gemini_key};
+use super::stronghold_store::{ProviderSettings, SecureKeyStore};
+use super::validation::{
+    validate_app_rules, validate_cleanup_pipeline, validate_deepgram_key, validate_gemini_key,
+    validate_http_policy, validate_language, validate_profile, validate_provider_settings,
+    validate_proxy_settings, validate_replacements, validate_spoken_punctuation,
+    validate_usage_quota,
+};
+use crate::core::language::{LanguageSetting, LANGUAGE_OPTIONS};
+use crate::core::merge::SecondOpinionSettings;
+use crate::core::orchestrator::DictationOrchestrator;
+use crate::core::pipeline::CleanupStage;
+use crate::core::profile::{self, CleanupProfile};
+use crate::focus::foreground::ForegroundApp;
+use crate::focus::rules::AppRule;
+use crate::history::store::{HistoryEntry, HistoryStore};
+use crate::network::http_client::{HttpClientFactory, HttpPolicy};
+use crate::network::proxy::ProxySettings;
+use crate::processors::registry::{
+    ProviderDescriptor, ProviderKind, ProviderRegistry, DEFAULT_CLEANUP_PROVIDER,
+    DEFAULT_TRANSCRIPTION_PROVIDER,
+};
+use crate::processors::replacements::{self, ExchangeFormat, Replacement};
+use crate::processors::spoken_punctuation::SpokenPunctuationSettings;
+use crate::processors::{deepgram, gemini};
+use crate::tray::language_menu::publish_language_change;
+use crate::tray::profile_menu::publish_profiles_change;
+use crate::usage::quota::UsageQuota;
+use crate::usage::store::{UsageStore, UsageSummary};
 
 pub struct SettingsState {
     store: Arc<dyn SecureKeyStore>,
+    registry: Arc<ProviderRegistry>,
+    http: Arc<HttpClientFactory>,
+    history: Arc<HistoryStore>,
+    usage: Arc<UsageStore>,
 }
 
 impl SettingsState {
-    pub fn new(store: Arc<dyn SecureKeyStore>) -> Self {
-        Self { store }
+    pub fn new(
+        store: Arc<dyn SecureKeyStore>,
+        registry: Arc<ProviderRegistry>,
+        http: Arc<HttpClientFactory>,
+        history: Arc<HistoryStore>,
+        usage: Arc<UsageStore>,
+    ) -> Self {
+        Self {
+            store,
+            registry,
+            http,
+            history,
+            usage,
+        }
     }
 }
 
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ProviderInfo {
+    #[serde(flatten)]
+    descriptor: ProviderDescriptor,
+    credentials_configured: bool,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ActiveProviders {
+    transcription: String,
+    cleanup: String,
+}
+
+#[derive(Serialize)]
+pub struct LanguageOption {
+    value: &'static str,
+    label: &'static str,
+}
+
 #[tauri::command]
 pub async fn save_deepgram_api_key(
     state: State<'_, SettingsState>,
     api_key: String,
 ) -> Result<(), String> {
     let trimmed_key = api_key.trim().to_string();
-    validate_deepgram_key(&trimmed_key).await?;
+    let http = state
+        .http
+        .client()
+        .await
+        .map_err(|err| format!("could not prepare http client: {err}"))?;
+    let settings = state
+        .store
+        .read_provider_settings(deepgram::PROVIDER_ID)
+        .await
+        .map_err(|err| format!("failed to read provider settings: {err}"))?;
+    validate_deepgram_key(&http, &deepgram::base_url(&settings), &trimmed_key).await?;
 
     state
         .store
@@ -56,7 +130,17 @@ pub async fn save_gemini_api_key(
     api_key: String,
 ) -> Result<(), String> {
     let trimmed_key = api_key.trim().to_string();
-    validate_gemini_key(&trimmed_key).await?;
+    let http = state
+        .http
+        .client()
+        .await
+        .map_err(|err| format!("could not prepare http client: {err}"))?;
+    let settings = state
+        .store
+        .read_provider_settings(gemini::PROVIDER_ID)
+        .await
+        .map_err(|err| format!("failed to read provider settings: {err}"))?;
+    validate_gemini_key(&http, &gemini::base_url(&settings), &trimmed_key).await?;
 
     state
         .store
@@ -84,6 +168,46 @@ pub async fn has_gemini_api_key(state: State<'_, SettingsState>) -> Result<bool,
         .map_err(|err| format!("failed to read key status: {err}"))
 }
 
+/// The key is optional: local servers such as Ollama accept requests without one.
+#[tauri::command]
+pub async fn save_openai_compatible_api_key(
+    state: State<'_, SettingsState>,
+    api_key: String,
+) -> Result<(), String> {
+    let trimmed_key = api_key.trim().to_string();
+    if trimmed_key.is_empty() {
+        return Err("API key is required.".to_string());
+    }
+    state
+        .store
+        .save_openai_compatible_key(trimmed_key)
+        .await
+        .map_err(|err| format!("failed to save key: {err}"))
+}
+
+#[tauri::command]
+pub async fn clear_openai_compatible_api_key(
+    state: State<'_, SettingsState>,
+) -> Result<(), String> {
+    state
+        .store
+        .clear_openai_compatible_key()
+        .await
+        .map_err(|err| format!("failed to clear key: {err}"))
+}
+
+#[tauri::command]
+pub async fn has_openai_compatible_api_key(
+    state: State<'_, SettingsState>,
+) -> Result<bool, String> {
+    state
+        .store
+        .read_openai_compatible_key()
+        .await
+        .map(|value| value.is_some())
+        .map_err(|err| format!("failed to read key status: {err}"))
+}
+
 #[tauri::command]
 pub async fn get_processing_enabled(state: State<'_, SettingsState>) -> Result<bool, String> {
     state
@@ -105,6 +229,69 @@ pub async fn set_processing_enabled(
         .map_err(|err| format!("failed to update processing setting: {err}"))
 }
 
+#[tauri::command]
+pub async fn get_review_enabled(state: State<'_, SettingsState>) -> Result<bool, String> {
+    state
+        .store
+        .read_review_enabled()
+        .await
+        .map_err(|err| format!("failed to read review setting: {err}"))
+}
+
+#[tauri::command]
+pub async fn set_review_enabled(
+    state: State<'_, SettingsState>,
+    enabled: bool,
+) -> Result<(), String> {
+    state
+        .store
+        .save_review_enabled(enabled)
+        .await
+        .map_err(|err| format!("failed to update review setting: {err}"))
+}
+
+#[tauri::command]
+pub async fn get_context_capture_enabled(state: State<'_, SettingsState>) -> Result<bool, String> {
+    state
+        .store
+        .read_context_capture_enabled()
+        .await
+        .map_err(|err| format!("failed to read context capture setting: {err}"))
+}
+
+#[tauri::command]
+pub async fn set_context_capture_enabled(
+    state: State<'_, SettingsState>,
+    enabled: bool,
+) -> Result<(), String> {
+    state
+        .store
+        .save_context_capture_enabled(enabled)
+        .await
+        .map_err(|err| format!("failed to update context capture setting: {err}"))
+}
+
+#[tauri::command]
+pub async fn get_command_mode_enabled(state: State<'_, SettingsState>) -> Result<bool, String> {
+    state
+        .store
+        .read_command_mode_enabled()
+        .await
+        .map_err(|err| format!("failed to read command mode setting: {err}"))
+}
+
+#[tauri::command]
+pub async fn set_command_mode_enabled(
+    state: State<'_, SettingsState>,
+    enabled: bool,
+) -> Result<(), String> {
+    state
+        .store
+        .save_command_mode_enabled(enabled)
+        .await
+        .map_err(|err| format!("failed to update command mode setting: {err}"))
+}
+
 #[tauri::command]
 pub async fn get_logging_enabled(state: State<'_, SettingsState>) -> Result<bool, String> {
     state
@@ -146,3 +333,623 @@ pub fn set_launch_on_startup_enabled(app: AppHandle, enabled: bool) -> Result<()
             .map_err(|err| format!("failed to disable launch on startup: {err}"))
     }
 }
+
+#[tauri::command]
+pub async fn list_providers(state: State<'_, SettingsState>) -> Result<Vec<ProviderInfo>, String> {
+    let mut providers = Vec::new();
+    for descriptor in state.registry.descriptors() {
+        let mut credentials_configured = true;
+        for credential in &descriptor.credentials {
+            if !credential
+                .is_present(state.store.as_ref())
+                .await
+                .map_err(|err| format!("failed to read key status: {err}"))?
+            {
+                credentials_configured = false;
+            }
+        }
+        providers.push(ProviderInfo {
+            descriptor,
+            credentials_configured,
+        });
+    }
+    Ok(providers)
+}
+
+#[tauri::command]
+pub async fn get_active_providers(
+    state: State<'_, SettingsState>,
+) -> Result<ActiveProviders, String> {
+    let transcription = state
+        .store
+        .read_transcription_provider()
+        .await
+        .map_err(|err| format!("failed to read transcription provider: {err}"))?
+        .unwrap_or_else(|| DEFAULT_TRANSCRIPTION_PROVIDER.to_string());
+    let cleanup = state
+        .store
+        .read_cleanup_provider()
+        .await
+        .map_err(|err| format!("failed to read cleanup provider: {err}"))?
+        .unwrap_or_else(|| DEFAULT_CLEANUP_PROVIDER.to_string());
+    Ok(ActiveProviders {
+        transcription,
+        cleanup,
+    })
+}
+
+#[tauri::command]
+pub async fn set_transcription_provider(
+    state: State<'_, SettingsState>,
+    provider_id: String,
+) -> Result<(), String> {
+    match state.registry.descriptor(&provider_id) {
+        Some(descriptor) if descriptor.kind == ProviderKind::Transcription => {}
+        _ => return Err(format!("Unknown transcription provider: {provider_id}")),
+    }
+    state
+        .store
+        .save_transcription_provider(provider_id)
+        .await
+        .map_err(|err| format!("failed to update transcription provider: {err}"))
+}
+
+#[tauri::command]
+pub async fn get_transcription_fallbacks(
+    state: State<'_, SettingsState>,
+) -> Result<Vec<String>, String> {
+    state
+        .store
+        .read_transcription_fallbacks()
+        .await
+        .map_err(|err| format!("failed to read fallback providers: {err}"))
+}
+
+#[tauri::command]
+pub async fn set_transcription_fallbacks(
+    state: State<'_, SettingsState>,
+    provider_ids: Vec<String>,
+) -> Result<(), String> {
+    for provider_id in &provider_ids {
+        match state.registry.descriptor(provider_id) {
+            Some(descriptor) if descriptor.kind == ProviderKind::Transcription => {}
+            _ => return Err(format!("Unknown transcription provider: {provider_id}")),
+        }
+    }
+    state
+        .store
+        .save_transcription_fallbacks(provider_ids)
+        .await
+        .map_err(|err| format!("failed to update fallback providers: {err}"))
+}
+
+#[tauri::command]
+pub async fn set_cleanup_provider(
+    state: State<'_, SettingsState>,
+    provider_id: String,
+) -> Result<(), String> {
+    match state.registry.descriptor(&provider_id) {
+        Some(descriptor) if descriptor.kind == ProviderKind::Cleanup => {}
+        _ => return Err(format!("Unknown cleanup provider: {provider_id}")),
+    }
+    state
+        .store
+        .save_cleanup_provider(provider_id)
+        .await
+        .map_err(|err| format!("failed to update cleanup provider: {err}"))
+}
+
+#[tauri::command]
+pub async fn get_provider_settings(
+    state: State<'_, SettingsState>,
+    provider_id: String,
+) -> Result<ProviderSettings, String> {
+    state
+        .store
+        .read_provider_settings(&provider_id)
+        .await
+        .map_err(|err| format!("failed to read provider settings: {err}"))
+}
+
+#[tauri::command]
+pub async fn set_provider_settings(
+    state: State<'_, SettingsState>,
+    provider_id: String,
+    settings: ProviderSettings,
+) -> Result<(), String> {
+    let descriptor = state
+        .registry
+        .descriptor(&provider_id)
+        .ok_or_else(|| format!("Unknown provider: {provider_id}"))?;
+    validate_provider_settings(descriptor, &settings)?;
+
+    state
+        .store
+        .save_provider_settings(provider_id, settings)
+        .await
+        .map_err(|err| format!("failed to update provider settings: {err}"))
+}
+
+#[tauri::command]
+pub async fn get_http_policy(state: State<'_, SettingsState>) -> Result<HttpPolicy, String> {
+    state
+        .store
+        .read_http_policy()
+        .await
+        .map_err(|err| format!("failed to read network settings: {err}"))
+}
+
+#[tauri::command]
+pub async fn set_http_policy(
+    state: State<'_, SettingsState>,
+    policy: HttpPolicy,
+) -> Result<(), String> {
+    validate_http_policy(&policy)?;
+    state
+        .store
+        .save_http_policy(policy)
+        .await
+        .map_err(|err| format!("failed to update network settings: {err}"))
+}
+
+#[tauri::command]
+pub async fn get_proxy_settings(state: State<'_, SettingsState>) -> Result<ProxySettings, String> {
+    state
+        .store
+        .read_proxy_settings()
+        .await
+        .map_err(|err| format!("failed to read proxy settings: {err}"))
+}
+
+#[tauri::command]
+pub async fn set_proxy_settings(
+    state: State<'_, SettingsState>,
+    settings: ProxySettings,
+) -> Result<(), String> {
+    validate_proxy_settings(&settings)?;
+    state
+        .store
+        .save_proxy_settings(settings)
+        .await
+        .map_err(|err| format!("failed to update proxy settings: {err}"))
+}
+
+#[tauri::command]
+pub async fn save_proxy_password(
+    state: State<'_, SettingsState>,
+    password: String,
+) -> Result<(), String> {
+    state
+        .store
+        .save_proxy_password(password)
+        .await
+        .map_err(|err| format!("failed to save proxy password: {err}"))
+}
+
+#[tauri::command]
+pub async fn clear_proxy_password(state: State<'_, SettingsState>) -> Result<(), String> {
+    state
+        .store
+        .clear_proxy_password()
+        .await
+        .map_err(|err| format!("failed to clear proxy password: {err}"))
+}
+
+#[tauri::command]
+pub async fn has_proxy_password(state: State<'_, SettingsState>) -> Result<bool, String> {
+    state
+        .store
+        .read_proxy_password()
+        .await
+        .map(|value| value.is_some())
+        .map_err(|err| format!("failed to read proxy password status: {err}"))
+}
+
+#[tauri::command]
+pub fn list_languages() -> Vec<LanguageOption> {
+    LANGUAGE_OPTIONS
+        .iter()
+        .map(|(value, label)| LanguageOption { value, label })
+        .collect()
+}
+
+#[tauri::command]
+pub async fn get_language(state: State<'_, SettingsState>) -> Result<String, String> {
+    state
+        .store
+        .read_language()
+        .await
+        .map(String::from)
+        .map_err(|err| format!("failed to read language setting: {err}"))
+}
+
+#[tauri::command]
+pub async fn set_language(
+    app: AppHandle,
+    state: State<'_, SettingsState>,
+    language: String,
+) -> Result<(), String> {
+    let language = LanguageSetting::from(validate_language(&language)?);
+    state
+        .store
+        .save_language(language.clone())
+        .await
+        .map_err(|err| format!("failed to update language setting: {err}"))?;
+    publish_language_change(&app, &language);
+    Ok(())
+}
+
+#[tauri::command]
+pub async fn get_second_opinion(
+    state: State<'_, SettingsState>,
+) -> Result<SecondOpinionSettings, String> {
+    state
+        .store
+        .read_second_opinion()
+        .await
+        .map_err(|err| format!("failed to read second opinion setting: {err}"))
+}
+
+#[tauri::command]
+pub async fn set_second_opinion(
+    state: State<'_, SettingsState>,
+    settings: SecondOpinionSettings,
+) -> Result<(), String> {
+    if let Some(provider_id) = &settings.provider {
+        match state.registry.descriptor(provider_id) {
+            Some(descriptor) if descriptor.kind == ProviderKind::Transcription => {}
+            _ => return Err(format!("Unknown transcription provider: {provider_id}")),
+        }
+    }
+    state
+        .store
+        .save_second_opinion(settings)
+        .await
+        .map_err(|err| format!("failed to update second opinion setting: {err}"))
+}
+
+#[tauri::command]
+pub async fn list_history(
+    state: State<'_, SettingsState>,
+    limit: usize,
+) -> Result<Vec<HistoryEntry>, String> {
+    state
+        .history
+        .list(limit)
+        .await
+        .map_err(|err| format!("failed to read history: {err}"))
+}
+
+#[tauri::command]
+pub async fn clear_history(state: State<'_, SettingsState>) -> Result<(), String> {
+    state
+        .history
+        .clear()
+        .await
+        .map_err(|err| format!("failed to clear history: {err}"))
+}
+
+#[tauri::command]
+pub async fn get_hallucination_blocklist(
+    state: State<'_, SettingsState>,
+) -> Result<Vec<String>, String> {
+    state
+        .store
+        .read_hallucination_blocklist()
+        .await
+        .map_err(|err| format!("failed to read blocklist: {err}"))
+}
+
+#[tauri::command]
+pub async fn set_hallucination_blocklist(
+    state: State<'_, SettingsState>,
+    phrases: Vec<String>,
+) -> Result<(), String> {
+    let phrases = phrases
+        .into_iter()
+        .map(|phrase| phrase.trim().to_string())
+        .filter(|phrase| !phrase.is_empty())
+        .collect();
+    state
+        .store
+        .save_hallucination_blocklist(phrases)
+        .await
+        .map_err(|err| format!("failed to update blocklist: {err}"))
+}
+
+#[tauri::command]
+pub async fn get_usage_summary(state: State<'_, SettingsState>) -> Result<UsageSummary, String> {
+    state
+        .usage
+        .summary()
+        .await
+        .map_err(|err| format!("failed to read usage: {err}"))
+}
+
+#[tauri::command]
+pub async fn get_usage_quota(state: State<'_, SettingsState>) -> Result<UsageQuota, String> {
+    state
+        .store
+        .read_usage_quota()
+        .await
+        .map_err(|err| format!("failed to read spending limit: {err}"))
+}
+
+#[tauri::command]
+pub async fn set_usage_quota(
+    state: State<'_, SettingsState>,
+    quota: UsageQuota,
+) -> Result<(), String> {
+    validate_usage_quota(&quota)?;
+    state
+        .store
+        .save_usage_quota(quota)
+        .await
+        .map_err(|err| format!("failed to update spending limit: {err}"))
+}
+
+#[tauri::command]
+pub async fn list_profiles(state: State<'_, SettingsState>) -> Result<Vec<CleanupProfile>, String> {
+    state
+        .store
+        .read_cleanup_profiles()
+        .await
+        .map_err(|err| format!("failed to read cleanup profiles: {err}"))
+}
+
+/// Adds the profile, or replaces the one with the same id.
+#[tauri::command]
+pub async fn save_profile(
+    app: AppHandle,
+    state: State<'_, SettingsState>,
+    profile: CleanupProfile,
+) -> Result<(), String> {
+    let profile = validate_profile(profile)?;
+    let translation_provider = profile
+        .translation
+        .as_ref()
+        .and_then(|translation| translation.provider.as_deref());
+    if let Some(id) = translation_provider {
+        if state.registry.cleaner(id).is_none() {
+            return Err(format!("Unknown translation provider: {id}"));
+        }
+    }
+    let mut profiles = state
+        .store
+        .read_cleanup_profiles()
+        .await
+        .map_err(|err| format!("failed to read cleanup profiles: {err}"))?;
+    match profiles
+        .iter_mut()
+        .find(|existing| existing.id == profile.id)
+    {
+        Some(existing) => *existing = profile,
+        None => profiles.push(profile),
+    }
+    state
+        .store
+        .save_cleanup_profiles(profiles)
+        .await
+        .map_err(|err| format!("failed to save cleanup profile: {err}"))?;
+    publish_profiles_change(&app, state.store.as_ref()).await;
+    Ok(())
+}
+
+/// Removes a profile. The last one cannot be deleted; deleting the active one switches to the
+/// default profile.
+#[tauri::command]
+pub async fn delete_profile(
+    app: AppHandle,
+    state: State<'_, SettingsState>,
+    profile_id: String,
+) -> Result<(), String> {
+    let mut profiles = state
+        .store
+        .read_cleanup_profiles()
+        .await
+        .map_err(|err| format!("failed to read cleanup profiles: {err}"))?;
+    let before = profiles.len();
+    profiles.retain(|profile| profile.id != profile_id);
+    if profiles.len() == before {
+        return Err(format!("Unknown cleanup profile: {profile_id}"));
+    }
+    if profiles.is_empty() {
+        return Err("At least one cleanup profile is required.".to_string());
+    }
+    let active_id = state
+        .store
+        .read_active_profile()
+        .await
+        .map_err(|err| format!("failed to read active profile: {err}"))?;
+    if active_id == profile_id {
+        let fallback = profile::resolve(&profiles, profile::DEFAULT_PROFILE_ID)
+            .map(|profile| profile.id.clone())
+            .unwrap_or_default();
+        state
+            .store
+            .save_active_profile(fallback)
+            .await
+            .map_err(|err| format!("failed to update active profile: {err}"))?;
+    }
+    state
+        .store
+        .save_cleanup_profiles(profiles)
+        .await
+        .map_err(|err| format!("failed to delete cleanup profile: {err}"))?;
+    publish_profiles_change(&app, state.store.as_ref()).await;
+    Ok(())
+}
+
+#[tauri::command]
+pub async fn get_active_profile(state: State<'_, SettingsState>) -> Result<String, String> {
+    let profiles = state
+        .store
+        .read_cleanup_profiles()
+        .await
+        .map_err(|err| format!("failed to read cleanup profiles: {err}"))?;
+    let active_id = state
+        .store
+        .read_active_profile()
+        .await
+        .map_err(|err| format!("failed to read active profile: {err}"))?;
+    Ok(profile::resolve(&profiles, &active_id)
+        .map(|profile| profile.id.clone())
+        .unwrap_or(active_id))
+}
+
+#[tauri::command]
+pub async fn set_active_profile(
+    app: AppHandle,
+    state: State<'_, SettingsState>,
+    profile_id: String,
+) -> Result<(), String> {
+    let profiles = state
+        .store
+        .read_cleanup_profiles()
+        .await
+        .map_err(|err| format!("failed to read cleanup profiles: {err}"))?;
+    if !profiles.iter().any(|profile| profile.id == profile_id) {
+        return Err(format!("Unknown cleanup profile: {profile_id}"));
+    }
+    state
+        .store
+        .save_active_profile(profile_id)
+        .await
+        .map_err(|err| format!("failed to update active profile: {err}"))?;
+    publish_profiles_change(&app, state.store.as_ref()).await;
+    Ok(())
+}
+
+#[tauri::command]
+pub async fn get_app_rules(state: State<'_, SettingsState>) -> Result<Vec<AppRule>, String> {
+    state
+        .store
+        .read_app_rules()
+        .await
+        .map_err(|err| format!("failed to read app rules: {err}"))
+}
+
+#[tauri::command]
+pub async fn set_app_rules(
+    state: State<'_, SettingsState>,
+    rules: Vec<AppRule>,
+) -> Result<(), String> {
+    let profiles = state
+        .store
+        .read_cleanup_profiles()
+        .await
+        .map_err(|err| format!("failed to read cleanup profiles: {err}"))?;
+    let rules = validate_app_rules(rules, &profiles)?;
+    state
+        .store
+        .save_app_rules(rules)
+        .await
+        .map_err(|err| format!("failed to update app rules: {err}"))
+}
+
+/// The application that was focused when the last recording started.
+#[tauri::command]
+pub async fn get_last_foreground_app(
+    orchestrator: State<'_, Arc<DictationOrchestrator>>,
+) -> Result<Option<ForegroundApp>, String> {
+    Ok(orchestrator.last_foreground_app().await)
+}
+
+#[tauri::command]
+pub async fn get_spoken_punctuation(
+    state: State<'_, SettingsState>,
+) -> Result<SpokenPunctuationSettings, String> {
+    state
+        .store
+        .read_spoken_punctuation()
+        .await
+        .map_err(|err| format!("failed to read spoken punctuation settings: {err}"))
+}
+
+#[tauri::command]
+pub async fn set_spoken_punctuation(
+    state: State<'_, SettingsState>,
+    settings: SpokenPunctuationSettings,
+) -> Result<(), String> {
+    let settings = validate_spoken_punctuation(settings)?;
+    state
+        .store
+        .save_spoken_punctuation(settings)
+        .await
+        .map_err(|err| format!("failed to update spoken punctuation settings: {err}"))
+}
+
+#[tauri::command]
+pub async fn get_cleanup_pipeline(
+    state: State<'_, SettingsState>,
+) -> Result<Vec<CleanupStage>, String> {
+    state
+        .store
+        .read_cleanup_pipeline()
+        .await
+        .map_err(|err| format!("failed to read cleanup pipeline: {err}"))
+}
+
+#[tauri::command]
+pub async fn set_cleanup_pipeline(
+    state: State<'_, SettingsState>,
+    stages: Vec<CleanupStage>,
+) -> Result<(), String> {
+    let stages = validate_cleanup_pipeline(stages)?;
+    state
+        .store
+        .save_cleanup_pipeline(stages)
+        .await
+        .map_err(|err| format!("failed to update cleanup pipeline: {err}"))
+}
+
+#[tauri::command]
+pub async fn get_replacements(state: State<'_, SettingsState>) -> Result<Vec<Replacement>, String> {
+    state
+        .store
+        .read_replacements()
+        .await
+        .map_err(|err| format!("failed to read replacements: {err}"))
+}
+
+#[tauri::command]
+pub async fn set_replacements(
+    state: State<'_, SettingsState>,
+    replacements: Vec<Replacement>,
+) -> Result<(), String> {
+    let replacements = validate_replacements(replacements)?;
+    state
+        .store
+        .save_replacements(replacements)
+        .await
+        .map_err(|err| format!("failed to update replacements: {err}"))
+}
+
+#[tauri::command]
+pub async fn export_replacements(
+    state: State<'_, SettingsState>,
+    format: ExchangeFormat,
+) -> Result<String, String> {
+    let rules = state
+        .store
+        .read_replacements()
+        .await
+        .map_err(|err| format!("failed to read replacements: {err}"))?;
+    replacements::export(&rules, format)
+}
+
+/// Replaces the whole table with the imported file and returns what was saved.
+#[tauri::command]
+pub async fn import_replacements(
+    state: State<'_, SettingsState>,
+    format: ExchangeFormat,
+    content: String,
+) -> Result<Vec<Replacement>, String> {
+    let rules = validate_replacements(replacements::import(&content, format)?)?;
+    state
+        .store
+        .save_replacements(rules.clone())
+        .await
+        .map_err(|err| format!("failed to update replacements: {err}"))?;
+    Ok(rules)
+}
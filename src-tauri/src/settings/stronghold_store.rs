@@ -1,3 +1,4 @@
+use std::collections::HashMap;
 use std::path::PathBuf;
 
 use anyhow::{Context, Result};
@@ -5,8 +6,23 @@ use async_trait::async_trait;
 use tauri::{AppHandle, Manager};
 use tokio::sync::Mutex;
 
+use crate::core::guard::DEFAULT_HALLUCINATION_PHRASES;
+use crate::core::language::LanguageSetting;
+use crate::core::merge::SecondOpinionSettings;
+use crate::core::pipeline::{self, CleanupStage, StageKind};
+use crate::core::profile::{self, CleanupProfile, DEFAULT_PROFILE_ID};
+use crate::focus::rules::AppRule;
+use crate::network::http_client::HttpPolicy;
+use crate::network::proxy::ProxySettings;
+use crate::processors::replacements::Replacement;
+use crate::processors::spoken_punctuation::SpokenPunctuationSettings;
+use crate::usage::quota::UsageQuota;
+
 const KEY_FILE: &str = "air-keys-credentials.json";
 
+/// Free-form per-provider settings, shaped by the provider's descriptor schema.
+pub type ProviderSettings = serde_json::Map<String, serde_json::Value>;
+
 #[async_trait]
 pub trait SecureKeyStore: Send + Sync {
     async fn save_deepgram_key(&self, api_key: String) -> Result<()>;
@@ -15,18 +31,99 @@ pub trait SecureKeyStore: Send + Sync {
     async fn save_gemini_key(&self, api_key: String) -> Result<()>;
     async fn read_gemini_key(&self) -> Result<Option<String>>;
     async fn clear_gemini_key(&self) -> Result<()>;
+    async fn save_openai_compatible_key(&self, api_key: String) -> Result<()>;
+    async fn read_openai_compatible_key(&self) -> Result<Option<String>>;
+    async fn clear_openai_compatible_key(&self) -> Result<()>;
     async fn save_processing_enabled(&self, enabled: bool) -> Result<()>;
     async fn read_processing_enabled(&self) -> Result<bool>;
     async fn save_logging_enabled(&self, enabled: bool) -> Result<()>;
     async fn read_logging_enabled(&self) -> Result<bool>;
+    async fn save_transcription_provider(&self, provider_id: String) -> Result<()>;
+    async fn read_transcription_provider(&self) -> Result<Option<String>>;
+    async fn save_transcription_fallbacks(&self, provider_ids: Vec<String>) -> Result<()>;
+    async fn read_transcription_fallbacks(&self) -> Result<Vec<String>>;
+    async fn save_cleanup_provider(&self, provider_id: String) -> Result<()>;
+    async fn read_cleanup_provider(&self) -> Result<Option<String>>;
+    async fn save_provider_settings(
+        &self,
+        provider_id: String,
+        settings: ProviderSettings,
+    ) -> Result<()>;
+    async fn read_provider_settings(&self, provider_id: &str) -> Result<ProviderSettings>;
+    async fn save_http_policy(&self, policy: HttpPolicy) -> Result<()>;
+    async fn read_http_policy(&self) -> Result<HttpPolicy>;
+    async fn save_proxy_settings(&self, settings: ProxySettings) -> Result<()>;
+    async fn read_proxy_settings(&self) -> Result<ProxySettings>;
+    async fn save_proxy_password(&self, password: String) -> Result<()>;
+    async fn read_proxy_password(&self) -> Result<Option<String>>;
+    async fn clear_proxy_password(&self) -> Result<()>;
+    async fn save_language(&self, language: LanguageSetting) -> Result<()>;
+    async fn read_language(&self) -> Result<LanguageSetting>;
+    async fn save_review_enabled(&self, enabled: bool) -> Result<()>;
+    async fn read_review_enabled(&self) -> Result<bool>;
+    async fn save_second_opinion(&self, settings: SecondOpinionSettings) -> Result<()>;
+    async fn read_second_opinion(&self) -> Result<SecondOpinionSettings>;
+    async fn save_hallucination_blocklist(&self, phrases: Vec<String>) -> Result<()>;
+    async fn read_hallucination_blocklist(&self) -> Result<Vec<String>>;
+    async fn save_usage_quota(&self, quota: UsageQuota) -> Result<()>;
+    async fn read_usage_quota(&self) -> Result<UsageQuota>;
+    async fn save_cleanup_profiles(&self, profiles: Vec<CleanupProfile>) -> Result<()>;
+    async fn read_cleanup_profiles(&self) -> Result<Vec<CleanupProfile>>;
+    async fn save_active_profile(&self, profile_id: String) -> Result<()>;
+    async fn read_active_profile(&self) -> Result<String>;
+    async fn save_app_rules(&self, rules: Vec<AppRule>) -> Result<()>;
+    async fn read_app_rules(&self) -> Result<Vec<AppRule>>;
+    async fn save_spoken_punctuation(&self, settings: SpokenPunctuationSettings) -> Result<()>;
+    async fn read_spoken_punctuation(&self) -> Result<SpokenPunctuationSettings>;
+    async fn save_cleanup_pipeline(&self, stages: Vec<CleanupStage>) -> Result<()>;
+    async fn read_cleanup_pipeline(&self) -> Result<Vec<CleanupStage>>;
+    async fn save_replacements(&self, replacements: Vec<Replacement>) -> Result<()>;
+    async fn read_replacements(&self) -> Result<Vec<Replacement>>;
+    async fn save_context_capture_enabled(&self, enabled: bool) -> Result<()>;
+    async fn read_context_capture_enabled(&self) -> Result<bool>;
+    async fn save_command_mode_enabled(&self, enabled: bool) -> Result<()>;
+    async fn read_command_mode_enabled(&self) -> Result<bool>;
 }
 
 #[derive(serde::Serialize, serde::Deserialize, Default)]
 struct KeyData {
     deepgram_api_key: Option<String>,
     gemini_api_key: Option<String>,
+    openai_compatible_api_key: Option<String>,
     processing_enabled: Option<bool>,
     logging_enabled: Option<bool>,
+    transcription_provider: Option<String>,
+    transcription_fallbacks: Option<Vec<String>>,
+    cleanup_provider: Option<String>,
+    #[serde(default)]
+    provider_settings: HashMap<String, ProviderSettings>,
+    http_policy: Option<HttpPolicy>,
+    proxy_settings: Option<ProxySettings>,
+    proxy_password: Option<String>,
+    language: Option<LanguageSetting>,
+    review_enabled: Option<bool>,
+    second_opinion: Option<SecondOpinionSettings>,
+    hallucination_blocklist: Option<Vec<String>>,
+    usage_quota: Option<UsageQuota>,
+    cleanup_profiles: Option<Vec<CleanupProfile>>,
+    active_profile: Option<String>,
+    app_rules: Option<Vec<AppRule>>,
+    spoken_punctuation: Option<SpokenPunctuationSettings>,
+    cleanup_pipeline: Option<Vec<CleanupStage>>,
+    replacements: Option<Vec<Replacement>>,
+    context_capture_enabled: Option<bool>,
+    command_mode_enabled: Option<bool>,
+}
+
+impl KeyData {
+    /// The saved pipeline, or the default one seeded from the older post-processing toggle.
+    fn cleanup_stages(&self) -> Vec<CleanupStage> {
+        let llm_enabled = self.processing_enabled.unwrap_or(false);
+        match &self.cleanup_pipeline {
+            Some(stages) => pipeline::with_missing_stages(stages.clone(), llm_enabled),
+            None => pipeline::default_stages(llm_enabled),
+        }
+    }
 }
 
 pub struct StrongholdStore {
@@ -65,6 +162,30 @@ impl StrongholdStore {
             .unwrap_or(false)
     }
 
+    /// Synchronous read used to seed the tray's language menu during setup.
+    pub fn read_language_blocking(&self) -> LanguageSetting {
+        self.data
+            .try_lock()
+            .ok()
+            .and_then(|data| data.language.clone())
+            .unwrap_or_default()
+    }
+
+    /// Synchronous read used to seed the tray's cleanup profile menu during setup.
+    pub fn read_profiles_blocking(&self) -> (Vec<CleanupProfile>, String) {
+        match self.data.try_lock() {
+            Ok(data) => (
+                data.cleanup_profiles
+                    .clone()
+                    .unwrap_or_else(profile::default_profiles),
+                data.active_profile
+                    .clone()
+                    .unwrap_or_else(|| DEFAULT_PROFILE_ID.to_string()),
+            ),
+            Err(_) => (profile::default_profiles(), DEFAULT_PROFILE_ID.to_string()),
+        }
+    }
+
     fn persist(file_path: &PathBuf, data: &KeyData) -> Result<()> {
         let contents = serde_json::to_string_pretty(data)
             .context("could not serialise credentials")?;
@@ -109,15 +230,43 @@ impl SecureKeyStore for StrongholdStore {
         Self::persist(&self.file_path, &data)
     }
 
+    async fn save_openai_compatible_key(&self, api_key: String) -> Result<()> {
+        let mut data = self.data.lock().await;
+        data.openai_compatible_api_key = Some(api_key);
+        Self::persist(&self.file_path, &data)
+    }
+
+    async fn read_openai_compatible_key(&self) -> Result<Option<String>> {
+        let data = self.data.lock().await;
+        Ok(data.openai_compatible_api_key.clone())
+    }
+
+    async fn clear_openai_compatible_key(&self) -> Result<()> {
+        let mut data = self.data.lock().await;
+        data.openai_compatible_api_key = None;
+        Self::persist(&self.file_path, &data)
+    }
+
+    /// Post-processing is the LLM stage of the cleanup pipeline; this toggles that stage.
     async fn save_processing_enabled(&self, enabled: bool) -> Result<()> {
         let mut data = self.data.lock().await;
+        let mut stages = data.cleanup_stages();
+        for stage in stages.iter_mut() {
+            if stage.kind == StageKind::Llm {
+                stage.enabled = enabled;
+            }
+        }
         data.processing_enabled = Some(enabled);
+        data.cleanup_pipeline = Some(stages);
         Self::persist(&self.file_path, &data)
     }
 
     async fn read_processing_enabled(&self) -> Result<bool> {
         let data = self.data.lock().await;
-        Ok(data.processing_enabled.unwrap_or(false))
+        Ok(data
+            .cleanup_stages()
+            .iter()
+            .any(|stage| stage.kind == StageKind::Llm && stage.enabled))
     }
 
     async fn save_logging_enabled(&self, enabled: bool) -> Result<()> {
@@ -130,4 +279,254 @@ impl SecureKeyStore for StrongholdStore {
         let data = self.data.lock().await;
         Ok(data.logging_enabled.unwrap_or(false))
     }
+
+    async fn save_transcription_provider(&self, provider_id: String) -> Result<()> {
+        let mut data = self.data.lock().await;
+        data.transcription_provider = Some(provider_id);
+        Self::persist(&self.file_path, &data)
+    }
+
+    async fn read_transcription_provider(&self) -> Result<Option<String>> {
+        let data = self.data.lock().await;
+        Ok(data.transcription_provider.clone())
+    }
+
+    async fn save_transcription_fallbacks(&self, provider_ids: Vec<String>) -> Result<()> {
+        let mut data = self.data.lock().await;
+        data.transcription_fallbacks = Some(provider_ids);
+        Self::persist(&self.file_path, &data)
+    }
+
+    async fn read_transcription_fallbacks(&self) -> Result<Vec<String>> {
+        let data = self.data.lock().await;
+        Ok(data.transcription_fallbacks.clone().unwrap_or_default())
+    }
+
+    async fn save_cleanup_provider(&self, provider_id: String) -> Result<()> {
+        let mut data = self.data.lock().await;
+        data.cleanup_provider = Some(provider_id);
+        Self::persist(&self.file_path, &data)
+    }
+
+    async fn read_cleanup_provider(&self) -> Result<Option<String>> {
+        let data = self.data.lock().await;
+        Ok(data.cleanup_provider.clone())
+    }
+
+    async fn save_provider_settings(
+        &self,
+        provider_id: String,
+        settings: ProviderSettings,
+    ) -> Result<()> {
+        let mut data = self.data.lock().await;
+        data.provider_settings.insert(provider_id, settings);
+        Self::persist(&self.file_path, &data)
+    }
+
+    async fn read_provider_settings(&self, provider_id: &str) -> Result<ProviderSettings> {
+        let data = self.data.lock().await;
+        Ok(data
+            .provider_settings
+            .get(provider_id)
+            .cloned()
+            .unwrap_or_default())
+    }
+
+    async fn save_http_policy(&self, policy: HttpPolicy) -> Result<()> {
+        let mut data = self.data.lock().await;
+        data.http_policy = Some(policy);
+        Self::persist(&self.file_path, &data)
+    }
+
+    async fn read_http_policy(&self) -> Result<HttpPolicy> {
+        let data = self.data.lock().await;
+        Ok(data.http_policy.clone().unwrap_or_default())
+    }
+
+    async fn save_proxy_settings(&self, settings: ProxySettings) -> Result<()> {
+        let mut data = self.data.lock().await;
+        data.proxy_settings = Some(settings);
+        Self::persist(&self.file_path, &data)
+    }
+
+    async fn read_proxy_settings(&self) -> Result<ProxySettings> {
+        let data = self.data.lock().await;
+        Ok(data.proxy_settings.clone().unwrap_or_default())
+    }
+
+    async fn save_proxy_password(&self, password: String) -> Result<()> {
+        let mut data = self.data.lock().await;
+        data.proxy_password = Some(password);
+        Self::persist(&self.file_path, &data)
+    }
+
+    async fn read_proxy_password(&self) -> Result<Option<String>> {
+        let data = self.data.lock().await;
+        Ok(data.proxy_password.clone())
+    }
+
+    async fn clear_proxy_password(&self) -> Result<()> {
+        let mut data = self.data.lock().await;
+        data.proxy_password = None;
+        Self::persist(&self.file_path, &data)
+    }
+
+    async fn save_language(&self, language: LanguageSetting) -> Result<()> {
+        let mut data = self.data.lock().await;
+        data.language = Some(language);
+        Self::persist(&self.file_path, &data)
+    }
+
+    async fn read_language(&self) -> Result<LanguageSetting> {
+        let data = self.data.lock().await;
+        Ok(data.language.clone().unwrap_or_default())
+    }
+
+    async fn save_review_enabled(&self, enabled: bool) -> Result<()> {
+        let mut data = self.data.lock().await;
+        data.review_enabled = Some(enabled);
+        Self::persist(&self.file_path, &data)
+    }
+
+    async fn read_review_enabled(&self) -> Result<bool> {
+        let data = self.data.lock().await;
+        Ok(data.review_enabled.unwrap_or(false))
+    }
+
+    async fn save_second_opinion(&self, settings: SecondOpinionSettings) -> Result<()> {
+        let mut data = self.data.lock().await;
+        data.second_opinion = Some(settings);
+        Self::persist(&self.file_path, &data)
+    }
+
+    async fn read_second_opinion(&self) -> Result<SecondOpinionSettings> {
+        let data = self.data.lock().await;
+        Ok(data.second_opinion.clone().unwrap_or_default())
+    }
+
+    async fn save_hallucination_blocklist(&self, phrases: Vec<String>) -> Result<()> {
+        let mut data = self.data.lock().await;
+        data.hallucination_blocklist = Some(phrases);
+        Self::persist(&self.file_path, &data)
+    }
+
+    async fn read_hallucination_blocklist(&self) -> Result<Vec<String>> {
+        let data = self.data.lock().await;
+        Ok(data.hallucination_blocklist.clone().unwrap_or_else(|| {
+            DEFAULT_HALLUCINATION_PHRASES
+                .iter()
+                .map(|phrase| phrase.to_string())
+                .collect()
+        }))
+    }
+
+    async fn save_usage_quota(&self, quota: UsageQuota) -> Result<()> {
+        let mut data = self.data.lock().await;
+        data.usage_quota = Some(quota);
+        Self::persist(&self.file_path, &data)
+    }
+
+    async fn read_usage_quota(&self) -> Result<UsageQuota> {
+        let data = self.data.lock().await;
+        Ok(data.usage_quota.clone().unwrap_or_default())
+    }
+
+    async fn save_cleanup_profiles(&self, profiles: Vec<CleanupProfile>) -> Result<()> {
+        let mut data = self.data.lock().await;
+        data.cleanup_profiles = Some(profiles);
+        Self::persist(&self.file_path, &data)
+    }
+
+    async fn read_cleanup_profiles(&self) -> Result<Vec<CleanupProfile>> {
+        let data = self.data.lock().await;
+        Ok(data
+            .cleanup_profiles
+            .clone()
+            .unwrap_or_else(profile::default_profiles))
+    }
+
+    async fn save_active_profile(&self, profile_id: String) -> Result<()> {
+        let mut data = self.data.lock().await;
+        data.active_profile = Some(profile_id);
+        Self::persist(&self.file_path, &data)
+    }
+
+    async fn read_active_profile(&self) -> Result<String> {
+        let data = self.data.lock().await;
+        Ok(data
+            .active_profile
+            .clone()
+            .unwrap_or_else(|| DEFAULT_PROFILE_ID.to_string()))
+    }
+
+    async fn save_app_rules(&self, rules: Vec<AppRule>) -> Result<()> {
+        let mut data = self.data.lock().await;
+        data.app_rules = Some(rules);
+        Self::persist(&self.file_path, &data)
+    }
+
+    async fn read_app_rules(&self) -> Result<Vec<AppRule>> {
+        let data = self.data.lock().await;
+        Ok(data.app_rules.clone().unwrap_or_default())
+    }
+
+    async fn save_spoken_punctuation(&self, settings: SpokenPunctuationSettings) -> Result<()> {
+        let mut data = self.data.lock().await;
+        data.spoken_punctuation = Some(settings);
+        Self::persist(&self.file_path, &data)
+    }
+
+    async fn read_spoken_punctuation(&self) -> Result<SpokenPunctuationSettings> {
+        let data = self.data.lock().await;
+        Ok(data.spoken_punctuation.clone().unwrap_or_default())
+    }
+
+    async fn save_cleanup_pipeline(&self, stages: Vec<CleanupStage>) -> Result<()> {
+        let mut data = self.data.lock().await;
+        data.processing_enabled = Some(
+            stages
+                .iter()
+                .any(|stage| stage.kind == StageKind::Llm && stage.enabled),
+        );
+        data.cleanup_pipeline = Some(stages);
+        Self::persist(&self.file_path, &data)
+    }
+
+    async fn read_cleanup_pipeline(&self) -> Result<Vec<CleanupStage>> {
+        let data = self.data.lock().await;
+        Ok(data.cleanup_stages())
+    }
+
+    async fn save_replacements(&self, replacements: Vec<Replacement>) -> Result<()> {
+        let mut data = self.data.lock().await;
+        data.replacements = Some(replacements);
+        Self::persist(&self.file_path, &data)
+    }
+
+    async fn read_replacements(&self) -> Result<Vec<Replacement>> {
+        let data = self.data.lock().await;
+        Ok(data.replacements.clone().unwrap_or_default())
+    }
+
+    async fn save_context_capture_enabled(&self, enabled: bool) -> Result<()> {
+        let mut data = self.data.lock().await;
+        data.context_capture_enabled = Some(enabled);
+        Self::persist(&self.file_path, &data)
+    }
+
+    async fn read_context_capture_enabled(&self) -> Result<bool> {
+        let data = self.data.lock().await;
+        Ok(data.context_capture_enabled.unwrap_or(false))
+    }
+
+    async fn save_command_mode_enabled(&self, enabled: bool) -> Result<()> {
+        let mut data = self.data.lock().await;
+        data.command_mode_enabled = Some(enabled);
+        Self::persist(&self.file_path, &data)
+    }
+
+    async fn read_command_mode_enabled(&self) -> Result<bool> {
+        let data = self.data.lock().await;
+        Ok(data.command_mode_enabled.unwrap_or(false))
+    }
 }
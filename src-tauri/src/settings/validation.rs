@@ -1,20 +1,39 @@
 use reqwest::header::{AUTHORIZATION, HeaderValue};
 use reqwest::StatusCode;
 
-const DEEPGRAM_VALIDATE_ENDPOINT: &str = "https://api.deepgram.com/v1/auth/token";
-const GEMINI_VALIDATE_ENDPOINT: &str = "https://generativelanguage.googleapis.com/v1/models";
+use crate::core::pipeline::{CleanupStage, StageKind};
+use crate::core::profile::CleanupProfile;
+use crate::focus::rules::AppRule;
+use crate::network::http_client::{HttpClient, HttpPolicy};
+use crate::network::proxy::ProxySettings;
+use crate::processors::deepgram;
+use crate::processors::registry::{ProviderDescriptor, SettingFieldKind};
+use crate::processors::replacements::{self, Replacement};
+use crate::processors::spoken_punctuation::SpokenPunctuationSettings;
+use crate::settings::stronghold_store::ProviderSettings;
+use crate::usage::quota::UsageQuota;
 
-pub async fn validate_deepgram_key(api_key: &str) -> Result<(), String> {
+const DEEPGRAM_VALIDATE_PATH: &str = "/v1/auth/token";
+const GEMINI_VALIDATE_PATH: &str = "/v1/models";
+
+/// Checks the key against the same API root transcription uses, so a regional or self-hosted
+/// deployment validates against itself.
+pub async fn validate_deepgram_key(
+    http: &HttpClient,
+    base_url: &str,
+    api_key: &str,
+) -> Result<(), String> {
     let trimmed_key = api_key.trim();
     if trimmed_key.is_empty() {
         return Err("Deepgram API key is required.".to_string());
     }
 
-    let client = reqwest::Client::new();
-    let response = client
-        .get(DEEPGRAM_VALIDATE_ENDPOINT)
-        .header(AUTHORIZATION, format!("Token {trimmed_key}"))
-        .send()
+    let response = http
+        .send(|client| {
+            client
+                .get(format!("{base_url}{DEEPGRAM_VALIDATE_PATH}"))
+                .header(AUTHORIZATION, format!("Token {trimmed_key}"))
+        })
         .await
         .map_err(|err| format!("could not validate Deepgram API key: {err}"))?;
 
@@ -22,6 +41,12 @@ pub async fn validate_deepgram_key(api_key: &str) -> Result<(), String> {
         return Ok(());
     }
 
+    // Self-hosted deployments have no key management endpoint to ask.
+    if response.status() == StatusCode::NOT_FOUND && !deepgram::is_cloud_host(base_url) {
+        log::info!("deepgram key not checked: {base_url} has no key endpoint");
+        return Ok(());
+    }
+
     if matches!(
         response.status(),
         StatusCode::UNAUTHORIZED | StatusCode::FORBIDDEN
@@ -35,7 +60,11 @@ pub async fn validate_deepgram_key(api_key: &str) -> Result<(), String> {
     ))
 }
 
-pub async fn validate_gemini_key(api_key: &str) -> Result<(), String> {
+pub async fn validate_gemini_key(
+    http: &HttpClient,
+    base_url: &str,
+    api_key: &str,
+) -> Result<(), String> {
     let trimmed_key = api_key.trim();
     if trimmed_key.is_empty() {
         return Err("Gemini API key is required.".to_string());
@@ -44,11 +73,12 @@ pub async fn validate_gemini_key(api_key: &str) -> Result<(), String> {
     let api_key_header = HeaderValue::from_str(trimmed_key)
         .map_err(|_| "Gemini API key contains invalid characters.".to_string())?;
 
-    let client = reqwest::Client::new();
-    let response = client
-        .get(GEMINI_VALIDATE_ENDPOINT)
-        .header("x-goog-api-key", api_key_header)
-        .send()
+    let response = http
+        .send(|client| {
+            client
+                .get(format!("{base_url}{GEMINI_VALIDATE_PATH}"))
+                .header("x-goog-api-key", api_key_header.clone())
+        })
         .await
         .map_err(|err| format!("could not validate Gemini API key: {err}"))?;
 
@@ -68,3 +98,277 @@ pub async fn validate_gemini_key(api_key: &str) -> Result<(), String> {
         response.status()
     ))
 }
+
+pub fn validate_provider_settings(
+    descriptor: &ProviderDescriptor,
+    settings: &ProviderSettings,
+) -> Result<(), String> {
+    for (key, value) in settings {
+        let field = descriptor
+            .field(key)
+            .ok_or_else(|| format!("{} has no setting named \"{key}\".", descriptor.name))?;
+        let is_valid = match &field.kind {
+            SettingFieldKind::Text => value.is_string(),
+            SettingFieldKind::Select { options } => value
+                .as_str()
+                .is_some_and(|option| options.contains(&option)),
+            SettingFieldKind::Number { min, max } => value
+                .as_f64()
+                .is_some_and(|number| (*min..=*max).contains(&number)),
+            SettingFieldKind::Url { .. } => value.as_str().is_some_and(is_base_url),
+        };
+        if !is_valid {
+            return Err(format!(
+                "Invalid value for {} setting \"{}\".",
+                descriptor.name, field.label
+            ));
+        }
+    }
+    Ok(())
+}
+
+/// An absolute http(s) URL without query or fragment, so API paths can be appended to it.
+fn is_base_url(value: &str) -> bool {
+    reqwest::Url::parse(value.trim()).is_ok_and(|url| {
+        matches!(url.scheme(), "http" | "https")
+            && url.host_str().is_some()
+            && url.query().is_none()
+            && url.fragment().is_none()
+    })
+}
+
+pub fn validate_http_policy(policy: &HttpPolicy) -> Result<(), String> {
+    if !(500..=60_000).contains(&policy.connect_timeout_ms) {
+        return Err("Connect timeout must be between 0.5 and 60 seconds.".to_string());
+    }
+    if !(1_000..=600_000).contains(&policy.request_timeout_ms) {
+        return Err("Request timeout must be between 1 and 600 seconds.".to_string());
+    }
+    if policy.max_retries > 5 {
+        return Err("At most 5 retries are allowed.".to_string());
+    }
+    if policy.initial_backoff_ms == 0 || policy.initial_backoff_ms > policy.max_backoff_ms {
+        return Err(
+            "Initial backoff must be positive and no larger than the maximum backoff.".to_string(),
+        );
+    }
+    if policy.max_backoff_ms > 60_000 {
+        return Err("Maximum backoff must be at most 60 seconds.".to_string());
+    }
+    Ok(())
+}
+
+pub fn validate_proxy_settings(settings: &ProxySettings) -> Result<(), String> {
+    if let Some(url) = settings.url.as_deref().filter(|url| !url.trim().is_empty()) {
+        if !is_base_url(url) {
+            return Err("Proxy URL must be an http:// or https:// address.".to_string());
+        }
+    }
+    settings
+        .proxy(None)
+        .map_err(|err| format!("Invalid proxy settings: {err:#}"))?;
+    let certificates = settings
+        .certificates()
+        .map_err(|err| format!("Invalid certificates: {err:#}"))?;
+    let has_pem = settings
+        .ca_certificates_pem
+        .as_deref()
+        .is_some_and(|pem| !pem.trim().is_empty());
+    if has_pem && certificates.is_empty() {
+        return Err("No PEM certificates found in the trusted certificates field.".to_string());
+    }
+    Ok(())
+}
+
+pub fn validate_usage_quota(quota: &UsageQuota) -> Result<(), String> {
+    if quota
+        .monthly_limit_usd
+        .is_some_and(|limit| !limit.is_finite() || limit <= 0.0)
+    {
+        return Err("Monthly spending limit must be a positive amount.".to_string());
+    }
+    Ok(())
+}
+
+/// Trims the profile's text fields and checks they are usable. Ids are lowercase slugs so
+/// they can double as tray menu ids.
+pub fn validate_profile(mut profile: CleanupProfile) -> Result<CleanupProfile, String> {
+    profile.id = profile.id.trim().to_string();
+    profile.name = profile.name.trim().to_string();
+    profile.prompt = profile.prompt.trim().to_string();
+    profile.model = profile
+        .model
+        .map(|model| model.trim().to_string())
+        .filter(|model| !model.is_empty());
+    if profile.model.is_none() {
+        profile.model_provider = None;
+    }
+
+    let is_slug = profile
+        .id
+        .chars()
+        .all(|c| c.is_ascii_lowercase() || c.is_ascii_digit() || c == '_' || c == '-');
+    if profile.id.is_empty() || !is_slug {
+        return Err("Profile id must use lowercase letters, digits, '-' or '_' only.".to_string());
+    }
+    if profile.name.is_empty() {
+        return Err("Profile name cannot be empty.".to_string());
+    }
+    if profile.prompt.is_empty() {
+        return Err("Profile prompt cannot be empty.".to_string());
+    }
+    if profile.model.is_some() && profile.model_provider.is_none() {
+        return Err("Choose which provider the model override is for.".to_string());
+    }
+    if profile
+        .temperature
+        .is_some_and(|temperature| !(0.0..=2.0).contains(&temperature))
+    {
+        return Err("Profile temperature must be between 0 and 2.".to_string());
+    }
+    if let Some(translation) = &mut profile.translation {
+        translation.target = validate_forced_language(&translation.target)?;
+        translation.source = match translation.source.as_deref().map(str::trim) {
+            Some("") | None => None,
+            Some(source) => Some(validate_forced_language(source)?),
+        };
+        translation.provider = translation
+            .provider
+            .take()
+            .map(|provider| provider.trim().to_string())
+            .filter(|provider| !provider.is_empty());
+    }
+    Ok(profile)
+}
+
+/// Trims the matchers and checks each rule can match something and points at settings that
+/// exist.
+pub fn validate_app_rules(
+    rules: Vec<AppRule>,
+    profiles: &[CleanupProfile],
+) -> Result<Vec<AppRule>, String> {
+    rules
+        .into_iter()
+        .enumerate()
+        .map(|(index, mut rule)| {
+            let trimmed = |value: Option<String>| {
+                value
+                    .map(|value| value.trim().to_string())
+                    .filter(|value| !value.is_empty())
+            };
+            rule.process_name = trimmed(rule.process_name);
+            rule.window_class = trimmed(rule.window_class);
+            rule.profile = trimmed(rule.profile);
+            if rule.process_name.is_none() && rule.window_class.is_none() {
+                return Err(format!(
+                    "App rule {} needs a process name or window class.",
+                    index + 1
+                ));
+            }
+            if let Some(profile_id) = &rule.profile {
+                if !profiles.iter().any(|profile| &profile.id == profile_id) {
+                    return Err(format!("Unknown cleanup profile: {profile_id}"));
+                }
+            }
+            if let Some(language) = &rule.language {
+                validate_language(language.as_str())?;
+            }
+            Ok(rule)
+        })
+        .collect()
+}
+
+/// Trims phrases and language keys and rejects commands that could never match or render.
+pub fn validate_spoken_punctuation(
+    mut settings: SpokenPunctuationSettings,
+) -> Result<SpokenPunctuationSettings, String> {
+    let mut grammars = std::collections::BTreeMap::new();
+    for (language, mut commands) in std::mem::take(&mut settings.grammars) {
+        let language = validate_language(&language.to_ascii_lowercase())?;
+        for command in &mut commands {
+            command.phrase = command
+                .phrase
+                .split_whitespace()
+                .collect::<Vec<_>>()
+                .join(" ");
+            if command.phrase.is_empty() {
+                return Err(format!(
+                    "A spoken command for \"{language}\" has no phrase."
+                ));
+            }
+            if command.text.is_empty() {
+                return Err(format!(
+                    "Spoken command \"{}\" has nothing to insert.",
+                    command.phrase
+                ));
+            }
+        }
+        grammars.insert(language, commands);
+    }
+    settings.grammars = grammars;
+    Ok(settings)
+}
+
+/// Drops blank rows and rejects entries with nothing to find or a pattern that does not compile.
+pub fn validate_replacements(rules: Vec<Replacement>) -> Result<Vec<Replacement>, String> {
+    let rules: Vec<Replacement> = rules
+        .into_iter()
+        .filter(|rule| !(rule.find.trim().is_empty() && rule.replace.trim().is_empty()))
+        .collect();
+    for (index, rule) in rules.iter().enumerate() {
+        if rule.find.trim().is_empty() {
+            return Err(format!("Replacement {} has nothing to find.", index + 1));
+        }
+        if let Err(err) = replacements::compile(std::slice::from_ref(rule)) {
+            return Err(format!(
+                "Replacement {} is not a valid pattern: {err}",
+                index + 1
+            ));
+        }
+    }
+    Ok(rules)
+}
+
+/// Requires every stage exactly once, each with a timeout between 100 ms and two minutes.
+pub fn validate_cleanup_pipeline(stages: Vec<CleanupStage>) -> Result<Vec<CleanupStage>, String> {
+    for kind in StageKind::ALL {
+        let count = stages.iter().filter(|stage| stage.kind == kind).count();
+        if count != 1 {
+            return Err(format!(
+                "The cleanup pipeline must list the {} stage exactly once.",
+                kind.as_str()
+            ));
+        }
+    }
+    if let Some(stage) = stages
+        .iter()
+        .find(|stage| !(100..=120_000).contains(&stage.timeout_ms))
+    {
+        return Err(format!(
+            "The {} stage timeout must be between 100 ms and 120000 ms.",
+            stage.kind.as_str()
+        ));
+    }
+    Ok(stages)
+}
+
+/// A single language code; "auto" and "multi" are not languages to translate from or into.
+fn validate_forced_language(language: &str) -> Result<String, String> {
+    match validate_language(language)?.as_str() {
+        "auto" | "multi" => Err("Translation needs a specific language.".to_string()),
+        code => Ok(code.to_string()),
+    }
+}
+
+/// Accepts "auto", "multi" or a BCP-47 style code such as "en" or "pt-BR".
+pub fn validate_language(language: &str) -> Result<String, String> {
+    let language = language.trim();
+    let is_code = (2..=8).contains(&language.len())
+        && language
+            .split('-')
+            .all(|part| !part.is_empty() && part.chars().all(|c| c.is_ascii_alphanumeric()));
+    if !is_code {
+        return Err(format!("Unsupported language: {language}"));
+    }
+    Ok(language.to_string())
+}
@@ -0,0 +1,78 @@
+use std::sync::Arc;
+
+use anyhow::Result;
+use tauri::menu::{CheckMenuItem, CheckMenuItemBuilder, Submenu, SubmenuBuilder};
+use tauri::{AppHandle, Emitter, Manager, Wry};
+
+use crate::core::language::{LanguageSetting, LANGUAGE_OPTIONS};
+use crate::settings::stronghold_store::SecureKeyStore;
+
+const MENU_ID_PREFIX: &str = "language:";
+const LANGUAGE_CHANGED_EVENT: &str = "language-changed";
+
+/// The tray's language check items, kept in app state so a change made in the settings window
+/// can move the check mark too.
+pub struct LanguageMenu {
+    items: Vec<CheckMenuItem<Wry>>,
+}
+
+impl LanguageMenu {
+    pub fn build(
+        app_handle: &AppHandle,
+        current: &LanguageSetting,
+    ) -> Result<(Self, Submenu<Wry>)> {
+        let mut submenu = SubmenuBuilder::new(app_handle, "Language");
+        let mut items = Vec::with_capacity(LANGUAGE_OPTIONS.len());
+        for (value, label) in LANGUAGE_OPTIONS {
+            let item = CheckMenuItemBuilder::with_id(format!("{MENU_ID_PREFIX}{value}"), *label)
+                .checked(current.as_str() == *value)
+                .build(app_handle)?;
+            submenu = submenu.item(&item);
+            if *value == "multi" {
+                submenu = submenu.separator();
+            }
+            items.push(item);
+        }
+        Ok((Self { items }, submenu.build()?))
+    }
+
+    pub fn sync(&self, current: &LanguageSetting) {
+        let checked_id = format!("{MENU_ID_PREFIX}{}", current.as_str());
+        for item in &self.items {
+            let _ = item.set_checked(item.id().as_ref() == checked_id);
+        }
+    }
+}
+
+/// Handles a tray menu event if it belongs to the language submenu. Returns `false` otherwise.
+pub fn handle_menu_event(
+    app_handle: &AppHandle,
+    key_store: Arc<dyn SecureKeyStore>,
+    menu_id: &str,
+) -> bool {
+    let Some(value) = menu_id.strip_prefix(MENU_ID_PREFIX) else {
+        return false;
+    };
+    let language = LanguageSetting::from(value.to_string());
+    let app_handle = app_handle.clone();
+    tauri::async_runtime::spawn(async move {
+        // Clicking a check item toggles it, so always re-apply the stored choice.
+        let current = match key_store.save_language(language.clone()).await {
+            Ok(()) => language,
+            Err(err) => {
+                log::warn!("failed to save language from tray: {err}");
+                key_store.read_language().await.unwrap_or_default()
+            }
+        };
+        publish_language_change(&app_handle, &current);
+    });
+    true
+}
+
+/// Moves the tray check mark and tells open windows, whichever side made the change.
+pub fn publish_language_change(app_handle: &AppHandle, language: &LanguageSetting) {
+    if let Some(menu) = app_handle.try_state::<LanguageMenu>() {
+        menu.sync(language);
+    }
+    let _ = app_handle.emit(LANGUAGE_CHANGED_EVENT, language.as_str());
+}
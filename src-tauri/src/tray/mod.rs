@@ -0,0 +1,2 @@
+pub mod language_menu;
+pub mod profile_menu;
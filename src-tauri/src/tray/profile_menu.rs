@@ -0,0 +1,95 @@
+use std::sync::Arc;
+
+use anyhow::Result;
+use tauri::menu::{CheckMenuItemBuilder, Submenu, SubmenuBuilder};
+use tauri::{AppHandle, Emitter, Manager, Wry};
+
+use crate::core::profile::{self, CleanupProfile};
+use crate::settings::stronghold_store::SecureKeyStore;
+
+const MENU_ID_PREFIX: &str = "profile:";
+const PROFILES_CHANGED_EVENT: &str = "profiles-changed";
+
+/// The tray's cleanup profile submenu. Profiles are user-defined, so the items are rebuilt
+/// whenever the list changes.
+pub struct ProfileMenu {
+    submenu: Submenu<Wry>,
+}
+
+impl ProfileMenu {
+    pub fn build(
+        app_handle: &AppHandle,
+        profiles: &[CleanupProfile],
+        active_id: &str,
+    ) -> Result<(Self, Submenu<Wry>)> {
+        let submenu = SubmenuBuilder::new(app_handle, "Cleanup profile").build()?;
+        let menu = Self {
+            submenu: submenu.clone(),
+        };
+        menu.sync(app_handle, profiles, active_id)?;
+        Ok((menu, submenu))
+    }
+
+    pub fn sync(
+        &self,
+        app_handle: &AppHandle,
+        profiles: &[CleanupProfile],
+        active_id: &str,
+    ) -> Result<()> {
+        for item in self.submenu.items()? {
+            self.submenu.remove(&item)?;
+        }
+        let active = profile::resolve(profiles, active_id).map(|profile| profile.id.as_str());
+        for profile in profiles {
+            let item = CheckMenuItemBuilder::with_id(
+                format!("{MENU_ID_PREFIX}{}", profile.id),
+                &profile.name,
+            )
+            .checked(active == Some(profile.id.as_str()))
+            .build(app_handle)?;
+            self.submenu.append(&item)?;
+        }
+        Ok(())
+    }
+}
+
+/// Handles a tray menu event if it belongs to the profile submenu. Returns `false` otherwise.
+pub fn handle_menu_event(
+    app_handle: &AppHandle,
+    key_store: Arc<dyn SecureKeyStore>,
+    menu_id: &str,
+) -> bool {
+    let Some(id) = menu_id.strip_prefix(MENU_ID_PREFIX) else {
+        return false;
+    };
+    let id = id.to_string();
+    let app_handle = app_handle.clone();
+    tauri::async_runtime::spawn(async move {
+        if let Err(err) = key_store.save_active_profile(id).await {
+            log::warn!("failed to save cleanup profile from tray: {err}");
+        }
+        // Clicking a check item toggles it, so always re-apply the stored choice.
+        publish_profiles_change(&app_handle, key_store.as_ref()).await;
+    });
+    true
+}
+
+/// Rebuilds the tray submenu and tells open windows, whichever side made the change.
+pub async fn publish_profiles_change(app_handle: &AppHandle, key_store: &dyn SecureKeyStore) {
+    let (profiles, active_id) = match tokio::try_join!(
+        key_store.read_cleanup_profiles(),
+        key_store.read_active_profile()
+    ) {
+        Ok(state) => state,
+        Err(err) => {
+            log::warn!("failed to read cleanup profiles: {err:#}");
+            return;
+        }
+    };
+    if let Some(menu) = app_handle.try_state::<ProfileMenu>() {
+        if let Err(err) = menu.sync(app_handle, &profiles, &active_id) {
+            log::warn!("failed to update cleanup profile menu: {err:#}");
+        }
+    }
+    let _ = app_handle.emit(PROFILES_CHANGED_EVENT, &active_id);
+}
@@ -0,0 +1,3 @@
+pub mod pricing;
+pub mod quota;
+pub mod store;
@@ -0,0 +1,166 @@
+use std::net::IpAddr;
+
+use crate::processors::deepgram;
+use crate::usage::store::UsageRecord;
+
+/// List prices in US dollars, used only for estimates. Providers bill on their own terms and
+/// these drift. Self-hosted servers are free; models and hosts missing from the tables are
+/// counted at the fallback rates, which err high, so they still count towards the limit.
+const DEEPGRAM_PER_MINUTE: f64 = 0.0043;
+const OPENAI_HOST: &str = "api.openai.com";
+const FALLBACK_AUDIO_PER_MINUTE: f64 = 0.006;
+const FALLBACK_TOKEN_PRICES: (f64, f64) = (2.50, 10.00);
+
+/// Per million input and output tokens.
+const GEMINI_TOKEN_PRICES: &[(&str, f64, f64)] = &[
+    ("gemini-3-flash-preview", 0.50, 3.00),
+    ("gemini-2.5-flash", 0.30, 2.50),
+    ("gemini-2.5-flash-lite", 0.10, 0.40),
+];
+
+const OPENAI_TOKEN_PRICES: &[(&str, f64, f64)] = &[
+    ("gpt-4o", 2.50, 10.00),
+    ("gpt-4o-mini", 0.15, 0.60),
+    ("gpt-4.1", 2.00, 8.00),
+    ("gpt-4.1-mini", 0.40, 1.60),
+    ("gpt-4.1-nano", 0.10, 0.40),
+];
+
+const OPENAI_AUDIO_PER_MINUTE: &[(&str, f64)] = &[
+    ("whisper-1", 0.006),
+    ("gpt-4o-transcribe", 0.006),
+    ("gpt-4o-mini-transcribe", 0.003),
+];
+
+pub fn estimated_cost(record: &UsageRecord) -> f64 {
+    match record.provider.as_str() {
+        // Self-hosted Deepgram is licensed separately, not billed per minute.
+        "deepgram" => match record.host.as_deref() {
+            Some(host) if !deepgram::is_cloud_hostname(host) => 0.0,
+            _ => audio_cost(record, DEEPGRAM_PER_MINUTE),
+        },
+        "gemini" => token_cost(record, GEMINI_TOKEN_PRICES),
+        // Records from before hosts were kept have none; they are most likely local servers.
+        "openai_compatible" | "whisper_server" => match record.host.as_deref() {
+            None => 0.0,
+            Some(host) if is_local_host(host) => 0.0,
+            Some(OPENAI_HOST) => {
+                token_cost(record, OPENAI_TOKEN_PRICES)
+                    + audio_cost(record, listed_audio_price(record, OPENAI_AUDIO_PER_MINUTE))
+            }
+            Some(_) => fallback_cost(record),
+        },
+        _ => fallback_cost(record),
+    }
+}
+
+fn audio_cost(record: &UsageRecord, per_minute: f64) -> f64 {
+    record.audio_seconds / 60.0 * per_minute
+}
+
+fn listed_audio_price(record: &UsageRecord, prices: &[(&str, f64)]) -> f64 {
+    prices
+        .iter()
+        .find(|(model, _)| record.model.as_deref() == Some(model))
+        .map_or(FALLBACK_AUDIO_PER_MINUTE, |(_, per_minute)| *per_minute)
+}
+
+fn token_cost(record: &UsageRecord, prices: &[(&str, f64, f64)]) -> f64 {
+    let (input, output) = prices
+        .iter()
+        .find(|(model, _, _)| record.model.as_deref() == Some(model))
+        .map_or(FALLBACK_TOKEN_PRICES, |(_, input, output)| {
+            (*input, *output)
+        });
+    (record.input_tokens as f64 * input + record.output_tokens as f64 * output) / 1_000_000.0
+}
+
+fn fallback_cost(record: &UsageRecord) -> f64 {
+    token_cost(record, &[]) + audio_cost(record, FALLBACK_AUDIO_PER_MINUTE)
+}
+
+/// Whether `host` names this computer or the local network, where servers are self-hosted.
+/// Single-label names such as `ollama` or `nas` only resolve locally.
+fn is_local_host(host: &str) -> bool {
+    let host = host.trim_start_matches('[').trim_end_matches(']');
+    match host.parse::<IpAddr>() {
+        Ok(IpAddr::V4(ip)) => ip.is_loopback() || ip.is_private() || ip.is_link_local(),
+        Ok(IpAddr::V6(ip)) => {
+            let first = ip.segments()[0];
+            ip.is_loopback() || first & 0xfe00 == 0xfc00 || first & 0xffc0 == 0xfe80
+        }
+        Err(_) => {
+            let host = host.to_ascii_lowercase();
+            !host.contains('.')
+                || [".localhost", ".local", ".lan", ".internal", ".home.arpa"]
+                    .iter()
+                    .any(|suffix| host.ends_with(suffix))
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn tokens(provider: &str, host: Option<&str>, model: &str) -> UsageRecord {
+        UsageRecord {
+            host: host.map(str::to_string),
+            ..UsageRecord::tokens(provider, Some(model.to_string()), 1_000_000, 1_000_000)
+        }
+    }
+
+    #[test]
+    fn self_hosted_servers_are_free() {
+        for host in [
+            "127.0.0.1",
+            "localhost",
+            "192.168.1.20",
+            "[::1]",
+            "ollama",
+            "nas.local",
+        ] {
+            let record = tokens("openai_compatible", Some(host), "llama3.1");
+            assert_eq!(estimated_cost(&record), 0.0, "{host}");
+        }
+    }
+
+    #[test]
+    fn openai_is_priced_from_its_list() {
+        let record = tokens("openai_compatible", Some(OPENAI_HOST), "gpt-4o-mini");
+        assert!((estimated_cost(&record) - 0.75).abs() < 1e-9);
+
+        let record = UsageRecord::audio("whisper_server", Some("whisper-1".into()), 120.0)
+            .on_host("https://api.openai.com/");
+        assert!((estimated_cost(&record) - 0.012).abs() < 1e-9);
+    }
+
+    #[test]
+    fn unknown_paid_hosts_and_models_use_the_fallback_rates() {
+        let expected = FALLBACK_TOKEN_PRICES.0 + FALLBACK_TOKEN_PRICES.1;
+        for record in [
+            tokens("openai_compatible", Some("openrouter.ai"), "gpt-4o-mini"),
+            tokens("openai_compatible", Some(OPENAI_HOST), "o3"),
+            tokens("gemini", None, "gemini-2.5-pro"),
+        ] {
+            assert!((estimated_cost(&record) - expected).abs() < 1e-9);
+        }
+    }
+
+    #[test]
+    fn self_hosted_deepgram_is_free() {
+        let audio = |base_url: &str| {
+            UsageRecord::audio("deepgram", Some("nova-3".into()), 600.0).on_host(base_url)
+        };
+        assert!((estimated_cost(&audio("https://api.deepgram.com")) - 0.043).abs() < 1e-9);
+        assert!((estimated_cost(&audio("https://api.eu.deepgram.com")) - 0.043).abs() < 1e-9);
+        assert_eq!(estimated_cost(&audio("http://10.0.0.5:8080")), 0.0);
+        assert_eq!(estimated_cost(&audio("https://stt.example.com")), 0.0);
+    }
+
+    #[test]
+    fn records_without_a_host_count_as_self_hosted() {
+        let record = tokens("openai_compatible", None, "gpt-4o");
+        assert_eq!(estimated_cost(&record), 0.0);
+    }
+}
@@ -0,0 +1,89 @@
+use std::sync::Arc;
+
+use serde::{Deserialize, Serialize};
+use tauri::{AppHandle, Emitter};
+
+use crate::core::audio_processor::AudioProcessorError;
+use crate::settings::stronghold_store::SecureKeyStore;
+use crate::usage::store::UsageStore;
+
+const USAGE_WARNING_EVENT: &str = "usage-warning";
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum QuotaMode {
+    /// Warn once the limit is reached but keep dictating.
+    #[default]
+    Soft,
+    /// Refuse billed provider calls once the limit is reached.
+    Hard,
+}
+
+/// Monthly spending limit on estimated cost. `monthly_limit_usd: None` turns it off.
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct UsageQuota {
+    pub monthly_limit_usd: Option<f64>,
+    #[serde(default)]
+    pub mode: QuotaMode,
+}
+
+impl UsageQuota {
+    /// The limit, when `spent` has reached it.
+    pub fn exceeded_by(&self, spent: f64) -> Option<f64> {
+        self.monthly_limit_usd.filter(|limit| spent >= *limit)
+    }
+}
+
+/// Checks the month's estimated spend against the configured quota before each billed
+/// provider call: transcription, cleanup, translation and command-mode rewrites.
+pub struct SpendingLimit {
+    app_handle: AppHandle,
+    key_store: Arc<dyn SecureKeyStore>,
+    usage: Arc<UsageStore>,
+}
+
+impl SpendingLimit {
+    pub fn new(
+        app_handle: AppHandle,
+        key_store: Arc<dyn SecureKeyStore>,
+        usage: Arc<UsageStore>,
+    ) -> Self {
+        Self {
+            app_handle,
+            key_store,
+            usage,
+        }
+    }
+
+    /// A soft quota only warns; a hard quota refuses the call.
+    pub async fn check(&self) -> Result<(), AudioProcessorError> {
+        let quota = self
+            .key_store
+            .read_usage_quota()
+            .await
+            .map_err(|err| AudioProcessorError::Request(err.to_string()))?;
+        if quota.monthly_limit_usd.is_none() {
+            return Ok(());
+        }
+        let spent = match self.usage.month_to_date_cost().await {
+            Ok(spent) => spent,
+            Err(err) => {
+                log::warn!("could not read usage; skipping spending limit: {err:#}");
+                return Ok(());
+            }
+        };
+        let Some(limit) = quota.exceeded_by(spent) else {
+            return Ok(());
+        };
+        let err = AudioProcessorError::SpendingLimitReached { limit, spent };
+        match quota.mode {
+            QuotaMode::Hard => Err(err),
+            QuotaMode::Soft => {
+                log::warn!("{err}");
+                let _ = self.app_handle.emit(USAGE_WARNING_EVENT, err.to_string());
+                Ok(())
+            }
+        }
+    }
+}
@@ -0,0 +1,284 @@
+use std::collections::BTreeMap;
+use std::fs::OpenOptions;
+use std::io::Write;
+use std::path::PathBuf;
+
+use anyhow::{Context, Result};
+use chrono::{DateTime, FixedOffset, Local};
+use serde::{Deserialize, Serialize};
+use tauri::{AppHandle, Manager};
+use tokio::sync::Mutex;
+
+use crate::usage::pricing;
+
+const USAGE_FILE: &str = "air-keys-usage.jsonl";
+const DAILY_PERIODS: usize = 31;
+const MONTHLY_PERIODS: usize = 12;
+
+/// One billable provider call.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct UsageRecord {
+    /// RFC 3339 timestamp in local time, so days and months follow the user's calendar.
+    pub timestamp: String,
+    pub provider: String,
+    pub model: Option<String>,
+    #[serde(default)]
+    pub audio_seconds: f64,
+    #[serde(default)]
+    pub input_tokens: u64,
+    #[serde(default)]
+    pub output_tokens: u64,
+    /// The server called, for providers whose url is configurable; pricing tells self-hosted
+    /// servers from paid ones by it.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub host: Option<String>,
+}
+
+impl UsageRecord {
+    pub fn audio(provider: &str, model: Option<String>, audio_seconds: f64) -> Self {
+        Self {
+            timestamp: Local::now().to_rfc3339(),
+            provider: provider.to_string(),
+            model,
+            audio_seconds,
+            input_tokens: 0,
+            output_tokens: 0,
+            host: None,
+        }
+    }
+
+    pub fn tokens(provider: &str, model: Option<String>, input: u64, output: u64) -> Self {
+        Self {
+            timestamp: Local::now().to_rfc3339(),
+            provider: provider.to_string(),
+            model,
+            audio_seconds: 0.0,
+            input_tokens: input,
+            output_tokens: output,
+            host: None,
+        }
+    }
+
+    pub fn on_host(mut self, base_url: &str) -> Self {
+        self.host = reqwest::Url::parse(base_url)
+            .ok()
+            .and_then(|url| url.host_str().map(str::to_ascii_lowercase));
+        self
+    }
+}
+
+#[derive(Clone, Debug, Default, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct UsageTotals {
+    pub audio_seconds: f64,
+    pub input_tokens: u64,
+    pub output_tokens: u64,
+    pub estimated_cost_usd: f64,
+}
+
+impl UsageTotals {
+    fn add(&mut self, record: &UsageRecord) {
+        self.audio_seconds += record.audio_seconds;
+        self.input_tokens += record.input_tokens;
+        self.output_tokens += record.output_tokens;
+        self.estimated_cost_usd += pricing::estimated_cost(record);
+    }
+}
+
+#[derive(Clone, Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ProviderUsage {
+    pub provider: String,
+    #[serde(flatten)]
+    pub totals: UsageTotals,
+}
+
+#[derive(Clone, Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct UsagePeriod {
+    /// `YYYY-MM-DD` for days, `YYYY-MM` for months.
+    pub period: String,
+    pub providers: Vec<ProviderUsage>,
+    pub estimated_cost_usd: f64,
+}
+
+/// Most recent periods first.
+#[derive(Clone, Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct UsageSummary {
+    pub daily: Vec<UsagePeriod>,
+    pub monthly: Vec<UsagePeriod>,
+}
+
+/// Estimated spend so far in `month` (`YYYY-MM`).
+struct MonthTotal {
+    month: String,
+    cost: f64,
+}
+
+/// Provider usage, one JSON object per line in the app's data directory.
+pub struct UsageStore {
+    file_path: PathBuf,
+    /// Serialises file access. Also caches the current month's spend, which the spending
+    /// limit reads before every billed call, so the file is only parsed once per month.
+    month_total: Mutex<Option<MonthTotal>>,
+}
+
+impl UsageStore {
+    pub fn new(app_handle: &AppHandle) -> Result<Self> {
+        let app_data = app_handle
+            .path()
+            .app_local_data_dir()
+            .context("could not resolve local data directory")?;
+        std::fs::create_dir_all(&app_data).context("could not create local data directory")?;
+        Ok(Self {
+            file_path: app_data.join(USAGE_FILE),
+            month_total: Mutex::new(None),
+        })
+    }
+
+    /// Appends a record. Accounting never fails a dictation, so errors are only logged.
+    pub async fn record(&self, record: UsageRecord) {
+        if let Err(err) = self.append(&record).await {
+            log::warn!("failed to record usage for {}: {err:#}", record.provider);
+        }
+    }
+
+    async fn append(&self, record: &UsageRecord) -> Result<()> {
+        let line = serde_json::to_string(record).context("could not serialise usage record")?;
+        let mut month_total = self.month_total.lock().await;
+        let mut file = OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(&self.file_path)
+            .context("could not open usage file")?;
+        writeln!(file, "{line}").context("could not write usage file")?;
+        if let Some(total) = month_total.as_mut() {
+            if Some(&total.month) == month_of(record).as_ref() {
+                total.cost += pricing::estimated_cost(record);
+            }
+        }
+        Ok(())
+    }
+
+    async fn records(&self) -> Result<Vec<(DateTime<FixedOffset>, UsageRecord)>> {
+        let _guard = self.month_total.lock().await;
+        self.read_records()
+    }
+
+    /// Lines that no longer parse are skipped. Callers hold the `month_total` lock.
+    fn read_records(&self) -> Result<Vec<(DateTime<FixedOffset>, UsageRecord)>> {
+        let contents = match std::fs::read_to_string(&self.file_path) {
+            Ok(contents) => contents,
+            Err(err) if err.kind() == std::io::ErrorKind::NotFound => return Ok(Vec::new()),
+            Err(err) => return Err(err).context("could not read usage file"),
+        };
+        Ok(contents
+            .lines()
+            .filter_map(|line| serde_json::from_str::<UsageRecord>(line).ok())
+            .filter_map(|record| {
+                let timestamp = DateTime::parse_from_rfc3339(&record.timestamp).ok()?;
+                Some((timestamp, record))
+            })
+            .collect())
+    }
+
+    pub async fn summary(&self) -> Result<UsageSummary> {
+        let records = self.records().await?;
+        Ok(UsageSummary {
+            daily: periods(&records, "%Y-%m-%d", DAILY_PERIODS),
+            monthly: periods(&records, "%Y-%m", MONTHLY_PERIODS),
+        })
+    }
+
+    /// Estimated spend in the current calendar month.
+    pub async fn month_to_date_cost(&self) -> Result<f64> {
+        let month = Local::now().format("%Y-%m").to_string();
+        let mut month_total = self.month_total.lock().await;
+        if let Some(total) = month_total.as_ref().filter(|total| total.month == month) {
+            return Ok(total.cost);
+        }
+        let cost = self
+            .read_records()?
+            .iter()
+            .filter(|(timestamp, _)| timestamp.format("%Y-%m").to_string() == month)
+            .map(|(_, record)| pricing::estimated_cost(record))
+            .sum();
+        *month_total = Some(MonthTotal { month, cost });
+        Ok(cost)
+    }
+}
+
+fn month_of(record: &UsageRecord) -> Option<String> {
+    DateTime::parse_from_rfc3339(&record.timestamp)
+        .ok()
+        .map(|timestamp| timestamp.format("%Y-%m").to_string())
+}
+
+fn periods(
+    records: &[(DateTime<FixedOffset>, UsageRecord)],
+    format: &str,
+    limit: usize,
+) -> Vec<UsagePeriod> {
+    let mut grouped: BTreeMap<String, BTreeMap<&str, UsageTotals>> = BTreeMap::new();
+    for (timestamp, record) in records {
+        grouped
+            .entry(timestamp.format(format).to_string())
+            .or_default()
+            .entry(record.provider.as_str())
+            .or_default()
+            .add(record);
+    }
+    grouped
+        .into_iter()
+        .rev()
+        .take(limit)
+        .map(|(period, providers)| UsagePeriod {
+            estimated_cost_usd: providers
+                .values()
+                .map(|totals| totals.estimated_cost_usd)
+                .sum(),
+            providers: providers
+                .into_iter()
+                .map(|(provider, totals)| ProviderUsage {
+                    provider: provider.to_string(),
+                    totals,
+                })
+                .collect(),
+            period,
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn month_total_follows_records_made_after_it_was_read() {
+        let file_path =
+            std::env::temp_dir().join(format!("air-keys-usage-test-{}.jsonl", std::process::id()));
+        let _ = std::fs::remove_file(&file_path);
+        let store = UsageStore {
+            file_path,
+            month_total: Mutex::new(None),
+        };
+
+        store
+            .record(UsageRecord::audio("deepgram", None, 60.0))
+            .await;
+        let first = store.month_to_date_cost().await.unwrap();
+        store
+            .record(UsageRecord::audio("deepgram", None, 60.0))
+            .await;
+        let cached = store.month_to_date_cost().await.unwrap();
+        *store.month_total.lock().await = None;
+        let reread = store.month_to_date_cost().await.unwrap();
+        let _ = std::fs::remove_file(&store.file_path);
+
+        assert!((first - 0.0043).abs() < 1e-9);
+        assert!((cached - 0.0086).abs() < 1e-9);
+        assert!((reread - cached).abs() < 1e-9);
+    }
+}
@@ -1,10 +1,18 @@
 import { useEffect, useMemo, useState } from "react"
 import { listen } from '@tauri-apps/api/event'
 
+import ReviewPanel from './ReviewPanel'
+import type { ReviewRequestPayload } from './ReviewPanel'
+
 type RecordingAmplitudePayload = {
     level: number
 }
 
+type AlternativesPayload = {
+    candidates: string[]
+    selected: number
+}
+
 type RecordingStatePayload = {
     state: 'listening' | 'processing' | 'cancelling'
 }
@@ -29,6 +37,8 @@ export default function RecordingIndicator() {
     const [displayLevel, setDisplayLevel] = useState(0)
     const [phase, setPhase] = useState(0)
     const [state, setState] = useState<'listening' | 'processing' | 'cancelling'>('listening')
+    const [review, setReview] = useState<ReviewRequestPayload | null>(null)
+    const [alternatives, setAlternatives] = useState<AlternativesPayload | null>(null)
 
     useEffect(() => {
         let mounted = true
@@ -49,6 +59,8 @@ export default function RecordingIndicator() {
                 if (!mounted) {
                     return
                 }
+                setReview(null)
+                setAlternatives(null)
                 if (event.payload.state === 'processing') {
                     setState('processing')
                     setTargetLevel(0)
@@ -80,6 +92,25 @@ export default function RecordingIndicator() {
         }
     }, [state])
 
+    useEffect(() => {
+        let detach: (() => void) | undefined
+        void Promise.all([
+            listen<ReviewRequestPayload>('review-requested', (event) => {
+                setReview(event.payload)
+            }),
+            listen<AlternativesPayload>('alternatives', (event) => {
+                setAlternatives(event.payload)
+            }),
+        ])
+            .then((unlisteners) => {
+                detach = () => unlisteners.forEach((unlisten) => unlisten())
+            })
+            .catch(() => {
+                // Running outside Tauri (e.g. plain browser preview) is non-fatal.
+            })
+        return () => detach?.()
+    }, [])
+
     useEffect(() => {
         let frame = 0
         const step = () => {
@@ -107,6 +138,34 @@ export default function RecordingIndicator() {
         })
     }, [displayLevel, phase, state])
 
+    if (review) {
+        return (
+            <ReviewPanel
+                key={review.transcript.requestId ?? review.transcript.text}
+                request={review}
+                onDecided={() => setReview(null)}
+            />
+        )
+    }
+
+    if (alternatives) {
+        return (
+            <main className="review-shell">
+                <ol className="alternatives-list">
+                    {alternatives.candidates.map((candidate, index) => (
+                        <li
+                            key={index}
+                            className={index === alternatives.selected ? 'alternative-selected' : ''}
+                        >
+                            {candidate}
+                        </li>
+                    ))}
+                </ol>
+                <p className="review-hint">Alt+` for the next one · pauses to paste the highlighted text</p>
+            </main>
+        )
+    }
+
     return (
         <main
             className={`recording-shell ${state === 'processing' || state === 'cancelling' ? 'recording-shell-processing' : ''}`}
@@ -0,0 +1,113 @@
+import { useEffect, useState } from 'react'
+import { invoke } from '@tauri-apps/api/core'
+
+export type TranscriptWord = {
+    text: string
+    start: number
+    end: number
+    confidence: number | null
+    alternatives: string[]
+}
+
+export type Transcript = {
+    text: string
+    words: TranscriptWord[]
+    language: string | null
+    provider: string
+    requestId: string | null
+}
+
+export type ReviewRequestPayload = {
+    transcript: Transcript
+    threshold: number
+}
+
+type ReviewDecision = { action: 'accept'; text: string } | { action: 'cancel' }
+
+type ReviewPanelProps = {
+    request: ReviewRequestPayload
+    onDecided: () => void
+}
+
+function isLowConfidence(word: TranscriptWord, threshold: number): boolean {
+    return word.confidence !== null && word.confidence < threshold
+}
+
+export default function ReviewPanel({ request, onDecided }: ReviewPanelProps) {
+    const { transcript, threshold } = request
+    const [words, setWords] = useState(() => transcript.words.map((word) => word.text))
+    const [openIndex, setOpenIndex] = useState<number | null>(null)
+
+    useEffect(() => {
+        const edited = words.some((word, index) => word !== transcript.words[index]?.text)
+        const decide = (decision: ReviewDecision) => {
+            void invoke('submit_review_decision', { decision })
+                .catch(() => {
+                    // The dictation may already have timed out; nothing left to do.
+                })
+                .finally(onDecided)
+        }
+        const onKeyDown = (event: KeyboardEvent) => {
+            if (event.key === 'Enter') {
+                event.preventDefault()
+                decide({ action: 'accept', text: edited ? words.join(' ') : transcript.text })
+            } else if (event.key === 'Escape') {
+                event.preventDefault()
+                if (openIndex !== null) {
+                    setOpenIndex(null)
+                } else {
+                    decide({ action: 'cancel' })
+                }
+            }
+        }
+        window.addEventListener('keydown', onKeyDown)
+        return () => window.removeEventListener('keydown', onKeyDown)
+    }, [words, openIndex, transcript, onDecided])
+
+    function pickAlternative(index: number, alternative: string) {
+        setWords((previous) => previous.map((word, i) => (i === index ? alternative : word)))
+        setOpenIndex(null)
+    }
+
+    return (
+        <main className="review-shell">
+            <p className="review-text">
+                {transcript.words.map((word, index) => {
+                    const low = isLowConfidence(word, threshold)
+                    const choices = [word.text, ...word.alternatives].filter(
+                        (choice) => choice !== words[index],
+                    )
+                    return (
+                        <span key={index} className="review-word-wrap">
+                            <span
+                                className={`review-word ${low ? 'review-word-low' : ''}`}
+                                onClick={() => setOpenIndex(openIndex === index ? null : index)}
+                            >
+                                {words[index]}
+                            </span>
+                            {openIndex === index && (
+                                <span className="review-alternatives">
+                                    {choices.length === 0 ? (
+                                        <span className="review-alternative-empty">No alternatives</span>
+                                    ) : (
+                                        choices.map((choice) => (
+                                            <button
+                                                key={choice}
+                                                type="button"
+                                                className="review-alternative"
+                                                onClick={() => pickAlternative(index, choice)}
+                                            >
+                                                {choice}
+                                            </button>
+                                        ))
+                                    )}
+                                </span>
+                            )}{' '}
+                        </span>
+                    )
+                })}
+            </p>
+            <p className="review-hint">Enter to paste · Esc to discard · click a word for alternatives</p>
+        </main>
+    )
+}
@@ -2,11 +2,262 @@ import { useEffect, useMemo, useState } from 'react'
 import type { FormEvent } from 'react'
 import { getVersion } from '@tauri-apps/api/app'
 import { invoke } from '@tauri-apps/api/core'
+import { listen } from '@tauri-apps/api/event'
 
 type SaveState = 'idle' | 'validating' | 'saving' | 'saved' | 'error'
 
+type UrlPreset = {
+    label: string
+    url: string
+}
+
+type SettingFieldKind =
+    | { type: 'text' }
+    | { type: 'select'; options: string[] }
+    | { type: 'number'; min: number; max: number }
+    | { type: 'url'; presets: UrlPreset[] }
+
+type SettingField = {
+    key: string
+    label: string
+    kind: SettingFieldKind
+    default: unknown
+}
+
+type ProviderInfo = {
+    id: string
+    name: string
+    kind: 'transcription' | 'cleanup'
+    credentials: string[]
+    settings: SettingField[]
+    credentialsConfigured: boolean
+}
+
+type ActiveProviders = {
+    transcription: string
+    cleanup: string
+}
+
+type ProviderSettings = Record<string, unknown>
+
+type ProviderErrorPayload = {
+    kind: string
+    provider: string | null
+    message: string
+}
+
+type HttpPolicy = {
+    connectTimeoutMs: number
+    requestTimeoutMs: number
+    maxRetries: number
+    initialBackoffMs: number
+    maxBackoffMs: number
+}
+
+const HTTP_POLICY_FIELDS: { key: keyof HttpPolicy; label: string; scale: number }[] = [
+    { key: 'connectTimeoutMs', label: 'Connect timeout (seconds)', scale: 1000 },
+    { key: 'requestTimeoutMs', label: 'Request timeout (seconds)', scale: 1000 },
+    { key: 'maxRetries', label: 'Retries', scale: 1 },
+    { key: 'initialBackoffMs', label: 'Initial backoff (seconds)', scale: 1000 },
+    { key: 'maxBackoffMs', label: 'Maximum backoff (seconds)', scale: 1000 },
+]
+
+type ProxySettings = {
+    url: string | null
+    username: string | null
+    noProxy: string
+    caCertificatesPem: string | null
+}
+
 const API_KEY_MASK = '••••••••••••'
 
+type LanguageOption = {
+    value: string
+    label: string
+}
+
+type MergeStrategy = 'higher_confidence' | 'word_by_word'
+
+type SecondOpinionSettings = {
+    provider: string | null
+    strategy: MergeStrategy
+}
+
+type Disagreement = {
+    primary: string | null
+    secondary: string | null
+    chosen: string | null
+    start: number
+}
+
+type HistoryEntry = {
+    timestamp: string
+    provider: string
+    text: string
+    secondOpinion: {
+        primaryProvider: string
+        secondaryProvider: string
+        primaryText: string
+        secondaryText: string
+        disagreements: Disagreement[]
+    } | null
+}
+
+const HISTORY_LIMIT = 20
+
+type ProviderUsage = {
+    provider: string
+    audioSeconds: number
+    inputTokens: number
+    outputTokens: number
+    estimatedCostUsd: number
+}
+
+type UsagePeriod = {
+    period: string
+    providers: ProviderUsage[]
+    estimatedCostUsd: number
+}
+
+type UsageSummary = {
+    daily: UsagePeriod[]
+    monthly: UsagePeriod[]
+}
+
+type UsageQuota = {
+    monthlyLimitUsd: number | null
+    mode: 'soft' | 'hard'
+}
+
+const USAGE_DAYS_SHOWN = 7
+
+type ProfileOutput = {
+    stripTrailingPeriod: boolean
+    singleLine: boolean
+}
+
+type ProfileTranslation = {
+    source: string | null
+    target: string
+    provider: string | null
+}
+
+type CleanupProfile = {
+    id: string
+    name: string
+    prompt: string
+    model: string | null
+    modelProvider: string | null
+    temperature: number | null
+    output: ProfileOutput
+    codeMode: boolean
+    translation: ProfileTranslation | null
+}
+
+type InjectionMethod = 'paste' | 'shift_paste' | 'type'
+
+type AppRule = {
+    processName: string | null
+    windowClass: string | null
+    profile: string | null
+    language: string | null
+    injection: InjectionMethod
+    trailingSpace: boolean
+    copyContext: boolean
+}
+
+type ForegroundApp = {
+    processName: string
+    windowClass: string
+}
+
+type Spacing = 'word' | 'attach_left' | 'attach_right' | 'break' | 'line_start'
+
+type SpokenCommand = {
+    phrase: string
+    text: string
+    spacing: Spacing
+    capitalizeNext: boolean
+}
+
+type SpokenPunctuationSettings = {
+    grammars: Record<string, SpokenCommand[]>
+}
+
+type MatchKind = 'literal' | 'regex'
+
+type Replacement = {
+    find: string
+    replace: string
+    kind: MatchKind
+    wholeWord: boolean
+    caseSensitive: boolean
+    preserveCase: boolean
+}
+
+type ExchangeFormat = 'csv' | 'json'
+
+type StageKind = 'replacements' | 'spoken_punctuation' | 'code' | 'llm' | 'translate' | 'casing'
+
+type FailurePolicy = 'skip' | 'abort'
+
+type CleanupStage = {
+    kind: StageKind
+    enabled: boolean
+    timeoutMs: number
+    onFailure: FailurePolicy
+}
+
+const STAGE_LABELS: Record<StageKind, string> = {
+    replacements: 'Replacements',
+    spoken_punctuation: 'Spoken punctuation',
+    code: 'Code mode',
+    translate: 'Translation',
+    llm: 'AI cleanup',
+    casing: 'Sentence casing',
+}
+
+const SPACING_LABELS: Record<Spacing, string> = {
+    word: 'Like a word',
+    attach_left: 'Joins previous word',
+    attach_right: 'Joins next word',
+    break: 'No spaces',
+    line_start: 'Starts a line',
+}
+
+// Line breaks are edited as "\n" so they stay visible in a single-line input.
+function showCommandText(text: string): string {
+    return text.replace(/\n/g, '\\n')
+}
+
+function parseCommandText(text: string): string {
+    return text.replace(/\\n/g, '\n')
+}
+
+function newProfileId(name: string, profiles: CleanupProfile[]): string {
+    const base =
+        name
+            .toLowerCase()
+            .replace(/[^a-z0-9]+/g, '_')
+            .replace(/^_+|_+$/g, '') || 'profile'
+    let id = base
+    for (let suffix = 2; profiles.some((profile) => profile.id === id); suffix += 1) {
+        id = `${base}_${suffix}`
+    }
+    return id
+}
+
+function describeUsage(usage: ProviderUsage): string {
+    const parts = []
+    if (usage.audioSeconds > 0) {
+        parts.push(`${(usage.audioSeconds / 60).toFixed(1)} min audio`)
+    }
+    if (usage.inputTokens > 0 || usage.outputTokens > 0) {
+        parts.push(`${usage.inputTokens} in / ${usage.outputTokens} out tokens`)
+    }
+    return `${usage.provider}: ${parts.join(', ') || 'no usage'} (~$${usage.estimatedCostUsd.toFixed(4)})`
+}
+
 function hasTauriInvoke(): boolean {
     if (typeof window === 'undefined') {
         return false
@@ -26,6 +277,33 @@ function parseInvokeError(error: unknown): string {
     return 'Unknown error'
 }
 
+function describeProviderError(error: ProviderErrorPayload, providers: ProviderInfo[]): string {
+    const name =
+        providers.find((provider) => provider.id === error.provider)?.name ??
+        error.provider ??
+        'A provider'
+    switch (error.kind) {
+        case 'unauthorized':
+            return `${name} rejected the stored API key. Clear it and enter a new key.`
+        case 'quota_exhausted':
+            return `${name} reports that your quota or credit is used up.`
+        case 'rate_limited':
+            return `${name} is rate limiting requests. Try again shortly.`
+        case 'payload_too_large':
+            return `${name} rejected the recording as too large.`
+        case 'timeout':
+            return `${name} did not respond in time.`
+        case 'network_unreachable':
+            return `Air Keys could not reach ${name}. Check your connection.`
+        case 'provider_server_error':
+            return `${name} is having problems right now.`
+        case 'spending_limit_reached':
+            return `Request blocked: ${error.message}. Raise the limit under Usage to continue.`
+        default:
+            return `Dictation failed: ${error.message}`
+    }
+}
+
 function isInvalidApiKeyError(message: string): boolean {
     return /(invalid|unauthorized|forbidden|401|403)/i.test(message)
 }
@@ -36,6 +314,8 @@ export function SettingsPage() {
 
     const hasDeepgramKey = deepgramApiKey === API_KEY_MASK
     const hasGeminiKey = geminiApiKey === API_KEY_MASK
+    const [openAiCompatibleApiKey, setOpenAiCompatibleApiKey] = useState('')
+    const hasOpenAiCompatibleKey = openAiCompatibleApiKey === API_KEY_MASK
 
     const [processingEnabled, setProcessingEnabled] = useState(false)
     const [appVersion, setAppVersion] = useState('...')
@@ -52,6 +332,37 @@ export function SettingsPage() {
     const [deepgramSaveState, setDeepgramSaveState] = useState<SaveState>('idle')
     const [geminiSaveState, setGeminiSaveState] = useState<SaveState>('idle')
     const [errorMessage, setErrorMessage] = useState('')
+    const [providers, setProviders] = useState<ProviderInfo[]>([])
+    const [activeProviders, setActiveProviders] = useState<ActiveProviders | null>(null)
+    const [providerSettings, setProviderSettings] = useState<Record<string, ProviderSettings>>({})
+    const [isSavingProviders, setIsSavingProviders] = useState(false)
+    const [transcriptionFallbacks, setTranscriptionFallbacks] = useState<string[]>([])
+    const [httpPolicy, setHttpPolicy] = useState<HttpPolicy | null>(null)
+    const [isSavingHttpPolicy, setIsSavingHttpPolicy] = useState(false)
+    const [proxySettings, setProxySettings] = useState<ProxySettings | null>(null)
+    const [hasProxyPassword, setHasProxyPassword] = useState(false)
+    const [languageOptions, setLanguageOptions] = useState<LanguageOption[]>([])
+    const [language, setLanguage] = useState('')
+    const [reviewEnabled, setReviewEnabled] = useState(false)
+    const [contextCaptureEnabled, setContextCaptureEnabled] = useState(false)
+    const [commandModeEnabled, setCommandModeEnabled] = useState(false)
+    const [secondOpinion, setSecondOpinion] = useState<SecondOpinionSettings>({
+        provider: null,
+        strategy: 'higher_confidence',
+    })
+    const [history, setHistory] = useState<HistoryEntry[]>([])
+    const [usageSummary, setUsageSummary] = useState<UsageSummary | null>(null)
+    const [usageQuota, setUsageQuota] = useState<UsageQuota>({ monthlyLimitUsd: null, mode: 'soft' })
+    const [blocklistText, setBlocklistText] = useState('')
+    const [profiles, setProfiles] = useState<CleanupProfile[]>([])
+    const [activeProfile, setActiveProfile] = useState('')
+    const [profileDraft, setProfileDraft] = useState<CleanupProfile | null>(null)
+    const [appRules, setAppRules] = useState<AppRule[]>([])
+    const [spokenPunctuation, setSpokenPunctuation] = useState<SpokenPunctuationSettings | null>(null)
+    const [grammarLanguage, setGrammarLanguage] = useState('en')
+    const [cleanupPipeline, setCleanupPipeline] = useState<CleanupStage[]>([])
+    const [replacements, setReplacements] = useState<Replacement[]>([])
+    const [lastForegroundApp, setLastForegroundApp] = useState<ForegroundApp | null>(null)
 
     const refreshDeepgramKeyStatus = async () => {
         setIsCheckingDeepgramStatus(true)
@@ -92,6 +403,14 @@ export function SettingsPage() {
         }
     }
 
+    const refreshOpenAiCompatibleKeyStatus = async () => {
+        if (!hasTauriInvoke()) {
+            throw new Error('Tauri runtime unavailable. Open this UI from the Air Keys tray app.')
+        }
+        const status = await invoke<boolean>('has_openai_compatible_api_key')
+        setOpenAiCompatibleApiKey(status ? API_KEY_MASK : '')
+    }
+
     const refreshProcessingEnabled = async () => {
         setIsCheckingProcessingEnabled(true)
         try {
@@ -118,14 +437,182 @@ export function SettingsPage() {
         }
     }
 
+    const refreshProviders = async () => {
+        if (!hasTauriInvoke()) {
+            throw new Error('Tauri runtime unavailable. Open this UI from the Air Keys tray app.')
+        }
+        const [available, active, fallbacks] = await Promise.all([
+            invoke<ProviderInfo[]>('list_providers'),
+            invoke<ActiveProviders>('get_active_providers'),
+            invoke<string[]>('get_transcription_fallbacks'),
+        ])
+        const settingsEntries = await Promise.all(
+            available.map(async (provider) => {
+                const settings = await invoke<ProviderSettings>('get_provider_settings', {
+                    providerId: provider.id,
+                })
+                return [provider.id, settings] as const
+            }),
+        )
+        setProviders(available)
+        setActiveProviders(active)
+        setTranscriptionFallbacks(fallbacks)
+        setProviderSettings(Object.fromEntries(settingsEntries))
+    }
+
+    const refreshHttpPolicy = async () => {
+        if (!hasTauriInvoke()) {
+            throw new Error('Tauri runtime unavailable. Open this UI from the Air Keys tray app.')
+        }
+        setHttpPolicy(await invoke<HttpPolicy>('get_http_policy'))
+    }
+
+    const refreshProxySettings = async () => {
+        if (!hasTauriInvoke()) {
+            throw new Error('Tauri runtime unavailable. Open this UI from the Air Keys tray app.')
+        }
+        const [settings, passwordStored] = await Promise.all([
+            invoke<ProxySettings>('get_proxy_settings'),
+            invoke<boolean>('has_proxy_password'),
+        ])
+        setProxySettings(settings)
+        setHasProxyPassword(passwordStored)
+    }
+
+    const refreshLanguage = async () => {
+        if (!hasTauriInvoke()) {
+            throw new Error('Tauri runtime unavailable. Open this UI from the Air Keys tray app.')
+        }
+        const [options, current] = await Promise.all([
+            invoke<LanguageOption[]>('list_languages'),
+            invoke<string>('get_language'),
+        ])
+        setLanguageOptions(options)
+        setLanguage(current)
+    }
+
+    const refreshReviewEnabled = async () => {
+        if (!hasTauriInvoke()) {
+            throw new Error('Tauri runtime unavailable. Open this UI from the Air Keys tray app.')
+        }
+        setReviewEnabled(await invoke<boolean>('get_review_enabled'))
+    }
+
+    const refreshContextCaptureEnabled = async () => {
+        if (!hasTauriInvoke()) {
+            throw new Error('Tauri runtime unavailable. Open this UI from the Air Keys tray app.')
+        }
+        setContextCaptureEnabled(await invoke<boolean>('get_context_capture_enabled'))
+    }
+
+    const refreshCommandModeEnabled = async () => {
+        if (!hasTauriInvoke()) {
+            throw new Error('Tauri runtime unavailable. Open this UI from the Air Keys tray app.')
+        }
+        setCommandModeEnabled(await invoke<boolean>('get_command_mode_enabled'))
+    }
+
+    const refreshSecondOpinion = async () => {
+        if (!hasTauriInvoke()) {
+            throw new Error('Tauri runtime unavailable. Open this UI from the Air Keys tray app.')
+        }
+        const [settings, entries] = await Promise.all([
+            invoke<SecondOpinionSettings>('get_second_opinion'),
+            invoke<HistoryEntry[]>('list_history', { limit: HISTORY_LIMIT }),
+        ])
+        setSecondOpinion(settings)
+        setHistory(entries)
+    }
+
+    const refreshUsage = async () => {
+        if (!hasTauriInvoke()) {
+            throw new Error('Tauri runtime unavailable. Open this UI from the Air Keys tray app.')
+        }
+        const [summary, quota] = await Promise.all([
+            invoke<UsageSummary>('get_usage_summary'),
+            invoke<UsageQuota>('get_usage_quota'),
+        ])
+        setUsageSummary(summary)
+        setUsageQuota(quota)
+    }
+
+    const refreshProfiles = async () => {
+        if (!hasTauriInvoke()) {
+            throw new Error('Tauri runtime unavailable. Open this UI from the Air Keys tray app.')
+        }
+        const [available, active] = await Promise.all([
+            invoke<CleanupProfile[]>('list_profiles'),
+            invoke<string>('get_active_profile'),
+        ])
+        setProfiles(available)
+        setActiveProfile(active)
+    }
+
+    const refreshAppRules = async () => {
+        if (!hasTauriInvoke()) {
+            throw new Error('Tauri runtime unavailable. Open this UI from the Air Keys tray app.')
+        }
+        const [rules, lastApp] = await Promise.all([
+            invoke<AppRule[]>('get_app_rules'),
+            invoke<ForegroundApp | null>('get_last_foreground_app'),
+        ])
+        setAppRules(rules)
+        setLastForegroundApp(lastApp)
+    }
+
+    const refreshSpokenPunctuation = async () => {
+        if (!hasTauriInvoke()) {
+            throw new Error('Tauri runtime unavailable. Open this UI from the Air Keys tray app.')
+        }
+        setSpokenPunctuation(await invoke<SpokenPunctuationSettings>('get_spoken_punctuation'))
+    }
+
+    const refreshReplacements = async () => {
+        if (!hasTauriInvoke()) {
+            throw new Error('Tauri runtime unavailable. Open this UI from the Air Keys tray app.')
+        }
+        setReplacements(await invoke<Replacement[]>('get_replacements'))
+    }
+
+    const refreshCleanupPipeline = async () => {
+        if (!hasTauriInvoke()) {
+            throw new Error('Tauri runtime unavailable. Open this UI from the Air Keys tray app.')
+        }
+        setCleanupPipeline(await invoke<CleanupStage[]>('get_cleanup_pipeline'))
+    }
+
+    const refreshBlocklist = async () => {
+        if (!hasTauriInvoke()) {
+            throw new Error('Tauri runtime unavailable. Open this UI from the Air Keys tray app.')
+        }
+        const phrases = await invoke<string[]>('get_hallucination_blocklist')
+        setBlocklistText(phrases.join('\n'))
+    }
+
     useEffect(() => {
         const timer = window.setTimeout(() => {
             void Promise.all([
                 refreshDeepgramKeyStatus(),
                 refreshLaunchOnStartup(),
                 refreshGeminiKeyStatus(),
+                refreshOpenAiCompatibleKeyStatus(),
                 refreshProcessingEnabled(),
                 refreshLoggingEnabled(),
+                refreshProviders(),
+                refreshHttpPolicy(),
+                refreshProxySettings(),
+                refreshLanguage(),
+                refreshReviewEnabled(),
+                refreshContextCaptureEnabled(),
+                refreshCommandModeEnabled(),
+                refreshSecondOpinion(),
+                refreshBlocklist(),
+                refreshUsage(),
+                refreshProfiles(),
+                refreshAppRules(),
+                refreshSpokenPunctuation(),
+                refreshCleanupPipeline(),
+                refreshReplacements(),
                 getVersion().then((version) => setAppVersion(version)),
             ]).catch((error) => {
                 setDeepgramSaveState('error')
@@ -135,6 +622,74 @@ export function SettingsPage() {
         return () => window.clearTimeout(timer)
     }, [])
 
+    useEffect(() => {
+        if (!hasTauriInvoke()) {
+            return
+        }
+        let detach: (() => void) | undefined
+        void listen<ProviderErrorPayload>('provider-error', (event) => {
+            setErrorMessage(describeProviderError(event.payload, providers))
+        })
+            .then((unlisten) => {
+                detach = unlisten
+            })
+            .catch(() => {
+                // Listening is best-effort; errors are also written to the log.
+            })
+        return () => detach?.()
+    }, [providers])
+
+    useEffect(() => {
+        if (!hasTauriInvoke()) {
+            return
+        }
+        let detach: (() => void) | undefined
+        void listen<string>('usage-warning', (event) => {
+            setErrorMessage(`Dictation continued, but the ${event.payload}.`)
+        })
+            .then((unlisten) => {
+                detach = unlisten
+            })
+            .catch(() => {
+                // Listening is best-effort; the warning is also written to the log.
+            })
+        return () => detach?.()
+    }, [])
+
+    useEffect(() => {
+        if (!hasTauriInvoke()) {
+            return
+        }
+        let detach: (() => void) | undefined
+        void listen<string>('language-changed', (event) => {
+            setLanguage(event.payload)
+        })
+            .then((unlisten) => {
+                detach = unlisten
+            })
+            .catch(() => {
+                // The tray and this page stay usable without live sync.
+            })
+        return () => detach?.()
+    }, [])
+
+    useEffect(() => {
+        if (!hasTauriInvoke()) {
+            return
+        }
+        let detach: (() => void) | undefined
+        void listen<string>('profiles-changed', () => {
+            void refreshProfiles().catch((error) => setErrorMessage(parseInvokeError(error)))
+        })
+            .then((unlisten) => {
+                detach = unlisten
+            })
+            .catch(() => {
+                // The tray and this page stay usable without live sync.
+            })
+        return () => detach?.()
+    }, [])
+
     const deepgramSaveButtonText = useMemo(() => {
         switch (deepgramSaveState) {
             case 'validating':
@@ -247,6 +802,33 @@ export function SettingsPage() {
         }
     }
 
+    async function onSaveOpenAiCompatible(event: FormEvent) {
+        event.preventDefault()
+        try {
+            setErrorMessage('')
+            if (!hasTauriInvoke()) {
+                throw new Error('Tauri runtime unavailable. Open this UI from the Air Keys tray app.')
+            }
+            await invoke('save_openai_compatible_api_key', { apiKey: openAiCompatibleApiKey })
+            setOpenAiCompatibleApiKey(API_KEY_MASK)
+        } catch (error) {
+            setErrorMessage(`Air Keys could not save the API key: ${parseInvokeError(error)}`)
+        }
+    }
+
+    async function onClearOpenAiCompatible() {
+        try {
+            if (!hasTauriInvoke()) {
+                throw new Error('Tauri runtime unavailable. Open this UI from the Air Keys tray app.')
+            }
+            await invoke('clear_openai_compatible_api_key')
+            setOpenAiCompatibleApiKey('')
+            setErrorMessage('')
+        } catch (error) {
+            setErrorMessage(`Air Keys could not clear the API key: ${parseInvokeError(error)}`)
+        }
+    }
+
     async function onLaunchOnStartupChange(enabled: boolean) {
         try {
             setIsSavingLaunchOnStartup(true)
@@ -275,6 +857,7 @@ export function SettingsPage() {
             }
             await invoke('set_processing_enabled', { enabled })
             setProcessingEnabled(enabled)
+            await refreshCleanupPipeline()
         } catch (error) {
             setDeepgramSaveState('error')
             setErrorMessage(`Air Keys could not update processing: ${parseInvokeError(error)}`)
@@ -299,61 +882,1668 @@ export function SettingsPage() {
         }
     }
 
-    return (
-        <main className="settings-shell">
-            <h1>Air Keys settings</h1>
-            <p className="settings-subtitle">Press Alt twice to start/stop dictation. <br />Hold Alt to cancel in progress recording.</p>
-            <br />
-            <p className="settings-help">
-                Air Keys runs from the system tray. Configure your transcription and post-processing
-                keys here.
-            </p>
+    async function onActiveProviderChange(kind: ProviderInfo['kind'], providerId: string) {
+        try {
+            setIsSavingProviders(true)
+            setErrorMessage('')
+            if (!hasTauriInvoke()) {
+                throw new Error('Tauri runtime unavailable. Open this UI from the Air Keys tray app.')
+            }
+            const command =
+                kind === 'transcription' ? 'set_transcription_provider' : 'set_cleanup_provider'
+            await invoke(command, { providerId })
+            setActiveProviders((previous) => (previous ? { ...previous, [kind]: providerId } : previous))
+        } catch (error) {
+            setErrorMessage(`Air Keys could not switch provider: ${parseInvokeError(error)}`)
+        } finally {
+            setIsSavingProviders(false)
+        }
+    }
 
-            <section className="settings-section">
-                <form className="settings-form" onSubmit={onSaveDeepgram}>
-                    <label htmlFor="deepgramKey">Deepgram API key</label>
-                    <input
-                        id="deepgramKey"
-                        type="password"
-                        autoComplete="off"
-                        spellCheck={false}
-                        value={deepgramApiKey}
-                        readOnly={hasDeepgramKey}
-                        onChange={(event) => {
-                            setDeepgramApiKey(event.target.value)
-                            if (deepgramSaveState !== 'idle') {
-                                setDeepgramSaveState('idle')
-                            }
-                        }}
-                        placeholder="dg_live_..."
-                    />
-                    <div className="settings-actions">
-                        <button
-                            type="submit"
-                            disabled={
-                                deepgramSaveState === 'validating' ||
-                                deepgramSaveState === 'saving' ||
-                                hasDeepgramKey
-                            }
+    async function onProviderSettingChange(providerId: string, key: string, value: unknown) {
+        const settings = { ...(providerSettings[providerId] ?? {}), [key]: value }
+        try {
+            setIsSavingProviders(true)
+            setErrorMessage('')
+            if (!hasTauriInvoke()) {
+                throw new Error('Tauri runtime unavailable. Open this UI from the Air Keys tray app.')
+            }
+            await invoke('set_provider_settings', { providerId, settings })
+            setProviderSettings((previous) => ({ ...previous, [providerId]: settings }))
+        } catch (error) {
+            setErrorMessage(`Air Keys could not update provider settings: ${parseInvokeError(error)}`)
+        } finally {
+            setIsSavingProviders(false)
+        }
+    }
+
+    function renderProviderPicker(kind: ProviderInfo['kind'], label: string) {
+        const options = providers.filter((provider) => provider.kind === kind)
+        const activeId = activeProviders?.[kind] ?? ''
+        const active = options.find((provider) => provider.id === activeId)
+        return (
+            <div className="settings-form settings-inline-form">
+                <label htmlFor={`${kind}Provider`}>{label}</label>
+                <select
+                    id={`${kind}Provider`}
+                    value={activeId}
+                    disabled={isSavingProviders || activeProviders === null}
+                    onChange={(event) => {
+                        void onActiveProviderChange(kind, event.target.value)
+                    }}
+                >
+                    {options.map((provider) => (
+                        <option key={provider.id} value={provider.id}>
+                            {provider.name}
+                            {provider.credentialsConfigured ? '' : ' (key not set)'}
+                        </option>
+                    ))}
+                </select>
+                {active ? renderProviderFields(active) : null}
+            </div>
+        )
+    }
+
+    function renderProviderFields(provider: ProviderInfo) {
+        return provider.settings.map((field) => {
+            const value = providerSettings[provider.id]?.[field.key] ?? field.default
+            const inputId = `${provider.id}-${field.key}`
+            return (
+                <div className="settings-form" key={field.key}>
+                    <label htmlFor={inputId}>{field.label}</label>
+                    {field.kind.type === 'select' ? (
+                        <select
+                            id={inputId}
+                            value={String(value)}
+                            disabled={isSavingProviders}
+                            onChange={(event) => {
+                                void onProviderSettingChange(provider.id, field.key, event.target.value)
+                            }}
                         >
-                            {deepgramSaveButtonText}
-                        </button>
-                        <button type="button" onClick={onClearDeepgram}>
-                            Clear key
-                        </button>
-                    </div>
-                </form>
-                <p className="settings-status">
-                    Stored key:{' '}
-                    <strong>
-                        {isCheckingDeepgramStatus ? 'checking...' : hasDeepgramKey ? 'present' : 'not set'}
-                    </strong>
-                </p>
-            </section>
+                            {field.kind.options.map((option) => (
+                                <option key={option} value={option}>
+                                    {option}
+                                </option>
+                            ))}
+                        </select>
+                    ) : field.kind.type === 'number' ? (
+                        <input
+                            id={inputId}
+                            type="number"
+                            min={field.kind.min}
+                            max={field.kind.max}
+                            step={0.1}
+                            defaultValue={Number(value)}
+                            disabled={isSavingProviders}
+                            onBlur={(event) => {
+                                const number = Number(event.target.value)
+                                if (Number.isFinite(number) && number !== Number(value)) {
+                                    void onProviderSettingChange(provider.id, field.key, number)
+                                }
+                            }}
+                        />
+                    ) : field.kind.type === 'url' ? (
+                        <>
+                            <select
+                                aria-label={`${field.label} preset`}
+                                value={field.kind.presets.find((preset) => preset.url === value)?.url ?? ''}
+                                disabled={isSavingProviders}
+                                onChange={(event) => {
+                                    if (event.target.value) {
+                                        void onProviderSettingChange(provider.id, field.key, event.target.value)
+                                    }
+                                }}
+                            >
+                                {field.kind.presets.map((preset) => (
+                                    <option key={preset.url} value={preset.url}>
+                                        {preset.label}
+                                    </option>
+                                ))}
+                                <option value="">Custom</option>
+                            </select>
+                            <input
+                                id={inputId}
+                                key={String(value)}
+                                type="url"
+                                spellCheck={false}
+                                defaultValue={String(value)}
+                                disabled={isSavingProviders}
+                                onBlur={(event) => {
+                                    if (event.target.value !== String(value)) {
+                                        void onProviderSettingChange(provider.id, field.key, event.target.value.trim())
+                                    }
+                                }}
+                            />
+                        </>
+                    ) : (
+                        <input
+                            id={inputId}
+                            type="text"
+                            spellCheck={false}
+                            defaultValue={String(value)}
+                            disabled={isSavingProviders}
+                            onBlur={(event) => {
+                                if (event.target.value !== String(value)) {
+                                    void onProviderSettingChange(provider.id, field.key, event.target.value)
+                                }
+                            }}
+                        />
+                    )}
+                </div>
+            )
+        })
+    }
 
-            <section className="settings-section">
-                <h2>General</h2>
-                <label className="settings-checkbox" htmlFor="launchOnStartup">
+    async function saveTranscriptionFallbacks(providerIds: string[]) {
+        try {
+            setIsSavingProviders(true)
+            setErrorMessage('')
+            if (!hasTauriInvoke()) {
+                throw new Error('Tauri runtime unavailable. Open this UI from the Air Keys tray app.')
+            }
+            await invoke('set_transcription_fallbacks', { providerIds })
+            setTranscriptionFallbacks(providerIds)
+        } catch (error) {
+            setErrorMessage(`Air Keys could not update fallback providers: ${parseInvokeError(error)}`)
+        } finally {
+            setIsSavingProviders(false)
+        }
+    }
+
+    function moveFallback(providerId: string, offset: -1 | 1) {
+        const index = transcriptionFallbacks.indexOf(providerId)
+        const target = index + offset
+        if (index < 0 || target < 0 || target >= transcriptionFallbacks.length) {
+            return
+        }
+        const reordered = [...transcriptionFallbacks]
+        reordered[index] = reordered[target]
+        reordered[target] = providerId
+        void saveTranscriptionFallbacks(reordered)
+    }
+
+    function renderFallbackList() {
+        const candidates = providers.filter(
+            (provider) =>
+                provider.kind === 'transcription' && provider.id !== activeProviders?.transcription,
+        )
+        if (candidates.length === 0) {
+            return null
+        }
+        const ordered = [
+            ...transcriptionFallbacks
+                .map((id) => candidates.find((provider) => provider.id === id))
+                .filter((provider): provider is ProviderInfo => provider !== undefined),
+            ...candidates.filter((provider) => !transcriptionFallbacks.includes(provider.id)),
+        ]
+        return (
+            <div className="settings-inline-form">
+                <p className="settings-muted">
+                    Fallbacks are tried in order when the transcription provider is unreachable,
+                    returns a server error or is out of quota.
+                </p>
+                {ordered.map((provider) => {
+                    const enabled = transcriptionFallbacks.includes(provider.id)
+                    return (
+                        <div className="settings-actions settings-checkbox-spaced" key={provider.id}>
+                            <label className="settings-checkbox" htmlFor={`fallback-${provider.id}`}>
+                                <input
+                                    id={`fallback-${provider.id}`}
+                                    type="checkbox"
+                                    checked={enabled}
+                                    disabled={isSavingProviders}
+                                    onChange={(event) => {
+                                        void saveTranscriptionFallbacks(
+                                            event.target.checked
+                                                ? [...transcriptionFallbacks, provider.id]
+                                                : transcriptionFallbacks.filter((id) => id !== provider.id),
+                                        )
+                                    }}
+                                />
+                                Fall back to {provider.name}
+                            </label>
+                            {enabled ? (
+                                <>
+                                    <button
+                                        type="button"
+                                        disabled={isSavingProviders}
+                                        onClick={() => moveFallback(provider.id, -1)}
+                                    >
+                                        Up
+                                    </button>
+                                    <button
+                                        type="button"
+                                        disabled={isSavingProviders}
+                                        onClick={() => moveFallback(provider.id, 1)}
+                                    >
+                                        Down
+                                    </button>
+                                </>
+                            ) : null}
+                        </div>
+                    )
+                })}
+                {ordered
+                    .filter((provider) => transcriptionFallbacks.includes(provider.id))
+                    .map((provider) => (
+                        <div key={provider.id}>{renderProviderFields(provider)}</div>
+                    ))}
+            </div>
+        )
+    }
+
+    async function onLanguageChange(value: string) {
+        try {
+            setIsSavingProviders(true)
+            setErrorMessage('')
+            if (!hasTauriInvoke()) {
+                throw new Error('Tauri runtime unavailable. Open this UI from the Air Keys tray app.')
+            }
+            await invoke('set_language', { language: value })
+            setLanguage(value)
+        } catch (error) {
+            setErrorMessage(`Air Keys could not change the language: ${parseInvokeError(error)}`)
+        } finally {
+            setIsSavingProviders(false)
+        }
+    }
+
+    async function onReviewEnabledChange(enabled: boolean) {
+        try {
+            setIsSavingProviders(true)
+            setErrorMessage('')
+            if (!hasTauriInvoke()) {
+                throw new Error('Tauri runtime unavailable. Open this UI from the Air Keys tray app.')
+            }
+            await invoke('set_review_enabled', { enabled })
+            setReviewEnabled(enabled)
+        } catch (error) {
+            setErrorMessage(`Air Keys could not update review mode: ${parseInvokeError(error)}`)
+        } finally {
+            setIsSavingProviders(false)
+        }
+    }
+
+    async function onContextCaptureEnabledChange(enabled: boolean) {
+        try {
+            setIsSavingProviders(true)
+            setErrorMessage('')
+            if (!hasTauriInvoke()) {
+                throw new Error('Tauri runtime unavailable. Open this UI from the Air Keys tray app.')
+            }
+            await invoke('set_context_capture_enabled', { enabled })
+            setContextCaptureEnabled(enabled)
+        } catch (error) {
+            setErrorMessage(`Air Keys could not update context capture: ${parseInvokeError(error)}`)
+        } finally {
+            setIsSavingProviders(false)
+        }
+    }
+
+    async function onCommandModeEnabledChange(enabled: boolean) {
+        try {
+            setIsSavingProviders(true)
+            setErrorMessage('')
+            if (!hasTauriInvoke()) {
+                throw new Error('Tauri runtime unavailable. Open this UI from the Air Keys tray app.')
+            }
+            await invoke('set_command_mode_enabled', { enabled })
+            setCommandModeEnabled(enabled)
+        } catch (error) {
+            setErrorMessage(`Air Keys could not update command mode: ${parseInvokeError(error)}`)
+        } finally {
+            setIsSavingProviders(false)
+        }
+    }
+
+    async function onSecondOpinionChange(settings: SecondOpinionSettings) {
+        try {
+            setIsSavingProviders(true)
+            setErrorMessage('')
+            if (!hasTauriInvoke()) {
+                throw new Error('Tauri runtime unavailable. Open this UI from the Air Keys tray app.')
+            }
+            await invoke('set_second_opinion', { settings })
+            setSecondOpinion(settings)
+        } catch (error) {
+            setErrorMessage(`Air Keys could not update the second opinion: ${parseInvokeError(error)}`)
+        } finally {
+            setIsSavingProviders(false)
+        }
+    }
+
+    async function onUsageQuotaChange(quota: UsageQuota) {
+        try {
+            setErrorMessage('')
+            if (!hasTauriInvoke()) {
+                throw new Error('Tauri runtime unavailable. Open this UI from the Air Keys tray app.')
+            }
+            await invoke('set_usage_quota', { quota })
+            setUsageQuota(quota)
+        } catch (error) {
+            setErrorMessage(`Air Keys could not update the spending limit: ${parseInvokeError(error)}`)
+        }
+    }
+
+    async function onActiveProfileChange(profileId: string) {
+        try {
+            setErrorMessage('')
+            if (!hasTauriInvoke()) {
+                throw new Error('Tauri runtime unavailable. Open this UI from the Air Keys tray app.')
+            }
+            await invoke('set_active_profile', { profileId })
+            setActiveProfile(profileId)
+        } catch (error) {
+            setErrorMessage(`Air Keys could not switch the cleanup profile: ${parseInvokeError(error)}`)
+        }
+    }
+
+    async function onSaveProfile(event: FormEvent) {
+        event.preventDefault()
+        if (!profileDraft) {
+            return
+        }
+        try {
+            setErrorMessage('')
+            if (!hasTauriInvoke()) {
+                throw new Error('Tauri runtime unavailable. Open this UI from the Air Keys tray app.')
+            }
+            await invoke('save_profile', { profile: profileDraft })
+            await refreshProfiles()
+            setProfileDraft(null)
+        } catch (error) {
+            setErrorMessage(`Air Keys could not save the cleanup profile: ${parseInvokeError(error)}`)
+        }
+    }
+
+    async function onDeleteProfile(profileId: string) {
+        try {
+            setErrorMessage('')
+            if (!hasTauriInvoke()) {
+                throw new Error('Tauri runtime unavailable. Open this UI from the Air Keys tray app.')
+            }
+            await invoke('delete_profile', { profileId })
+            await refreshProfiles()
+            setProfileDraft(null)
+        } catch (error) {
+            setErrorMessage(`Air Keys could not delete the cleanup profile: ${parseInvokeError(error)}`)
+        }
+    }
+
+    function renderProfileTranslation(translation: ProfileTranslation) {
+        if (!profileDraft) {
+            return null
+        }
+        const update = (changes: Partial<ProfileTranslation>) =>
+            setProfileDraft({ ...profileDraft, translation: { ...translation, ...changes } })
+        const languages = languageOptions.filter((option) => option.value !== 'auto' && option.value !== 'multi')
+        return (
+            <>
+                <label htmlFor="profileTranslationSource">Spoken language</label>
+                <select
+                    id="profileTranslationSource"
+                    value={translation.source ?? ''}
+                    onChange={(event) => update({ source: event.target.value || null })}
+                >
+                    <option value="">Dictation language setting</option>
+                    {languages.map((option) => (
+                        <option key={option.value} value={option.value}>
+                            {option.label}
+                        </option>
+                    ))}
+                </select>
+                <label htmlFor="profileTranslationTarget">Paste in</label>
+                <select
+                    id="profileTranslationTarget"
+                    value={translation.target}
+                    onChange={(event) => update({ target: event.target.value })}
+                >
+                    {languages.map((option) => (
+                        <option key={option.value} value={option.value}>
+                            {option.label}
+                        </option>
+                    ))}
+                </select>
+                <label htmlFor="profileTranslationProvider">Translate with</label>
+                <select
+                    id="profileTranslationProvider"
+                    value={translation.provider ?? ''}
+                    onChange={(event) => update({ provider: event.target.value || null })}
+                >
+                    <option value="">Post-processing provider</option>
+                    {providers
+                        .filter((provider) => provider.kind === 'cleanup')
+                        .map((provider) => (
+                            <option key={provider.id} value={provider.id}>
+                                {provider.name}
+                            </option>
+                        ))}
+                </select>
+                <p className="settings-muted">
+                    The translation prompt replaces this profile's prompt and cleans up the dictation as it translates.
+                </p>
+            </>
+        )
+    }
+
+    function renderProfiles() {
+        const isNewProfile = profileDraft !== null && !profiles.some((profile) => profile.id === profileDraft.id)
+        return (
+            <>
+                <div className="settings-form settings-inline-form">
+                    <label htmlFor="activeProfile">Cleanup profile</label>
+                    <select
+                        id="activeProfile"
+                        value={activeProfile}
+                        onChange={(event) => {
+                            void onActiveProfileChange(event.target.value)
+                        }}
+                    >
+                        {profiles.map((profile) => (
+                            <option key={profile.id} value={profile.id}>
+                                {profile.name}
+                            </option>
+                        ))}
+                    </select>
+                    <div className="settings-actions">
+                        <button
+                            type="button"
+                            onClick={() => {
+                                const current = profiles.find((profile) => profile.id === activeProfile)
+                                setProfileDraft(current ?? null)
+                            }}
+                        >
+                            Edit
+                        </button>
+                        <button
+                            type="button"
+                            onClick={() => {
+                                setProfileDraft({
+                                    id: '',
+                                    name: '',
+                                    prompt: profiles.find((profile) => profile.id === activeProfile)?.prompt ?? '',
+                                    model: null,
+                                    modelProvider: null,
+                                    temperature: null,
+                                    output: { stripTrailingPeriod: false, singleLine: false },
+                                    codeMode: false,
+                                    translation: null,
+                                })
+                            }}
+                        >
+                            New profile
+                        </button>
+                    </div>
+                </div>
+                <p className="settings-muted">
+                    The profile sets the cleanup prompt and style. You can also switch it from the tray menu.
+                </p>
+                {profileDraft ? (
+                    <form className="settings-form" onSubmit={onSaveProfile}>
+                        <label htmlFor="profileName">Name</label>
+                        <input
+                            id="profileName"
+                            value={profileDraft.name}
+                            onChange={(event) => {
+                                const name = event.target.value
+                                setProfileDraft({
+                                    ...profileDraft,
+                                    name,
+                                    id: isNewProfile ? newProfileId(name, profiles) : profileDraft.id,
+                                })
+                            }}
+                        />
+                        <label htmlFor="profilePrompt">Prompt</label>
+                        <textarea
+                            id="profilePrompt"
+                            rows={8}
+                            value={profileDraft.prompt}
+                            onChange={(event) => setProfileDraft({ ...profileDraft, prompt: event.target.value })}
+                        />
+                        <label htmlFor="profileModel">Model override</label>
+                        <input
+                            id="profileModel"
+                            spellCheck={false}
+                            placeholder="Use the provider setting"
+                            value={profileDraft.model ?? ''}
+                            onChange={(event) => {
+                                const model = event.target.value || null
+                                setProfileDraft({
+                                    ...profileDraft,
+                                    model,
+                                    modelProvider: model
+                                        ? profileDraft.modelProvider ?? activeProviders?.cleanup ?? null
+                                        : null,
+                                })
+                            }}
+                        />
+                        {profileDraft.model ? (
+                            <>
+                                <label htmlFor="profileModelProvider">Model is for</label>
+                                <select
+                                    id="profileModelProvider"
+                                    value={profileDraft.modelProvider ?? ''}
+                                    onChange={(event) =>
+                                        setProfileDraft({ ...profileDraft, modelProvider: event.target.value || null })
+                                    }
+                                >
+                                    <option value="" disabled>
+                                        Choose a provider
+                                    </option>
+                                    {providers
+                                        .filter((provider) => provider.kind === 'cleanup')
+                                        .map((provider) => (
+                                            <option key={provider.id} value={provider.id}>
+                                                {provider.name}
+                                            </option>
+                                        ))}
+                                </select>
+                                <p className="settings-muted">
+                                    Other providers, including one picked for translation, keep their own model.
+                                </p>
+                            </>
+                        ) : null}
+                        <label htmlFor="profileTemperature">Temperature override</label>
+                        <input
+                            id="profileTemperature"
+                            type="number"
+                            min={0}
+                            max={2}
+                            step={0.1}
+                            placeholder="Use the provider setting"
+                            value={profileDraft.temperature ?? ''}
+                            onChange={(event) =>
+                                setProfileDraft({
+                                    ...profileDraft,
+                                    temperature: event.target.value === '' ? null : Number(event.target.value),
+                                })
+                            }
+                        />
+                        <label className="settings-checkbox" htmlFor="profileStripPeriod">
+                            <input
+                                id="profileStripPeriod"
+                                type="checkbox"
+                                checked={profileDraft.output.stripTrailingPeriod}
+                                onChange={(event) =>
+                                    setProfileDraft({
+                                        ...profileDraft,
+                                        output: { ...profileDraft.output, stripTrailingPeriod: event.target.checked },
+                                    })
+                                }
+                            />
+                            Remove the trailing period
+                        </label>
+                        <label className="settings-checkbox" htmlFor="profileSingleLine">
+                            <input
+                                id="profileSingleLine"
+                                type="checkbox"
+                                checked={profileDraft.output.singleLine}
+                                onChange={(event) =>
+                                    setProfileDraft({
+                                        ...profileDraft,
+                                        output: { ...profileDraft.output, singleLine: event.target.checked },
+                                    })
+                                }
+                            />
+                            Join the result into a single line
+                        </label>
+                        <label className="settings-checkbox" htmlFor="profileCodeMode">
+                            <input
+                                id="profileCodeMode"
+                                type="checkbox"
+                                checked={profileDraft.codeMode}
+                                onChange={(event) => setProfileDraft({ ...profileDraft, codeMode: event.target.checked })}
+                            />
+                            Code mode: say "camel case user id" for userId and "equals equals" for ==
+                        </label>
+                        <label className="settings-checkbox" htmlFor="profileTranslate">
+                            <input
+                                id="profileTranslate"
+                                type="checkbox"
+                                checked={profileDraft.translation !== null}
+                                onChange={(event) =>
+                                    setProfileDraft({
+                                        ...profileDraft,
+                                        translation: event.target.checked
+                                            ? { source: null, target: 'en', provider: null }
+                                            : null,
+                                    })
+                                }
+                            />
+                            Translate the dictation
+                        </label>
+                        {profileDraft.translation ? renderProfileTranslation(profileDraft.translation) : null}
+                        <div className="settings-actions">
+                            <button type="submit">Save profile</button>
+                            {isNewProfile ? null : (
+                                <button type="button" onClick={() => void onDeleteProfile(profileDraft.id)}>
+                                    Delete profile
+                                </button>
+                            )}
+                            <button type="button" onClick={() => setProfileDraft(null)}>
+                                Cancel
+                            </button>
+                        </div>
+                    </form>
+                ) : null}
+            </>
+        )
+    }
+
+    async function onSaveAppRules(rules: AppRule[]) {
+        try {
+            setErrorMessage('')
+            if (!hasTauriInvoke()) {
+                throw new Error('Tauri runtime unavailable. Open this UI from the Air Keys tray app.')
+            }
+            await invoke('set_app_rules', { rules })
+            setAppRules(await invoke<AppRule[]>('get_app_rules'))
+        } catch (error) {
+            setErrorMessage(`Air Keys could not save the app rules: ${parseInvokeError(error)}`)
+        }
+    }
+
+    function renderAppRules() {
+        const updateRule = (index: number, changes: Partial<AppRule>) => {
+            setAppRules(appRules.map((rule, ruleIndex) => (ruleIndex === index ? { ...rule, ...changes } : rule)))
+        }
+        const addRule = (app: ForegroundApp | null) => {
+            setAppRules([
+                ...appRules,
+                {
+                    processName: app?.processName ?? '',
+                    windowClass: app?.windowClass ?? null,
+                    profile: null,
+                    language: null,
+                    injection: 'paste',
+                    trailingSpace: false,
+                    copyContext: false,
+                },
+            ])
+        }
+        return (
+            <>
+                {appRules.map((rule, index) => (
+                    <div className="settings-history-entry" key={index}>
+                        <div className="settings-form settings-inline-form">
+                            <div className="settings-form">
+                                <label htmlFor={`ruleProcess-${index}`}>Process name</label>
+                                <input
+                                    id={`ruleProcess-${index}`}
+                                    spellCheck={false}
+                                    placeholder="e.g. WindowsTerminal.exe"
+                                    value={rule.processName ?? ''}
+                                    onChange={(event) => updateRule(index, { processName: event.target.value || null })}
+                                />
+                            </div>
+                            <div className="settings-form">
+                                <label htmlFor={`ruleClass-${index}`}>Window class</label>
+                                <input
+                                    id={`ruleClass-${index}`}
+                                    spellCheck={false}
+                                    placeholder="Any"
+                                    value={rule.windowClass ?? ''}
+                                    onChange={(event) => updateRule(index, { windowClass: event.target.value || null })}
+                                />
+                            </div>
+                        </div>
+                        <div className="settings-form settings-inline-form">
+                            <div className="settings-form">
+                                <label htmlFor={`ruleProfile-${index}`}>Cleanup profile</label>
+                                <select
+                                    id={`ruleProfile-${index}`}
+                                    value={rule.profile ?? ''}
+                                    onChange={(event) => updateRule(index, { profile: event.target.value || null })}
+                                >
+                                    <option value="">Active profile</option>
+                                    {profiles.map((profile) => (
+                                        <option key={profile.id} value={profile.id}>
+                                            {profile.name}
+                                        </option>
+                                    ))}
+                                </select>
+                            </div>
+                            <div className="settings-form">
+                                <label htmlFor={`ruleLanguage-${index}`}>Language</label>
+                                <select
+                                    id={`ruleLanguage-${index}`}
+                                    value={rule.language ?? ''}
+                                    onChange={(event) => updateRule(index, { language: event.target.value || null })}
+                                >
+                                    <option value="">Global language</option>
+                                    {languageOptions.map((option) => (
+                                        <option key={option.value} value={option.value}>
+                                            {option.label}
+                                        </option>
+                                    ))}
+                                </select>
+                            </div>
+                            <div className="settings-form">
+                                <label htmlFor={`ruleInjection-${index}`}>Insert text by</label>
+                                <select
+                                    id={`ruleInjection-${index}`}
+                                    value={rule.injection}
+                                    onChange={(event) =>
+                                        updateRule(index, { injection: event.target.value as InjectionMethod })
+                                    }
+                                >
+                                    <option value="paste">Pasting (Ctrl+V)</option>
+                                    <option value="shift_paste">Terminal paste (Ctrl+Shift+V)</option>
+                                    <option value="type">Typing</option>
+                                </select>
+                            </div>
+                        </div>
+                        <label className="settings-checkbox" htmlFor={`ruleTrailingSpace-${index}`}>
+                            <input
+                                id={`ruleTrailingSpace-${index}`}
+                                type="checkbox"
+                                checked={rule.trailingSpace}
+                                onChange={(event) => updateRule(index, { trailingSpace: event.target.checked })}
+                            />
+                            Add a space after the text
+                        </label>
+                        <label className="settings-checkbox" htmlFor={`ruleCopyContext-${index}`}>
+                            <input
+                                id={`ruleCopyContext-${index}`}
+                                type="checkbox"
+                                checked={rule.copyContext}
+                                onChange={(event) => updateRule(index, { copyContext: event.target.checked })}
+                            />
+                            Copy the line before the cursor for context when its text is not readable
+                        </label>
+                        <div className="settings-actions">
+                            <button
+                                type="button"
+                                onClick={() => setAppRules(appRules.filter((_, ruleIndex) => ruleIndex !== index))}
+                            >
+                                Remove rule
+                            </button>
+                        </div>
+                    </div>
+                ))}
+                <div className="settings-actions">
+                    <button type="button" onClick={() => addRule(null)}>
+                        Add rule
+                    </button>
+                    {lastForegroundApp ? (
+                        <button type="button" onClick={() => addRule(lastForegroundApp)}>
+                            Add rule for {lastForegroundApp.processName || lastForegroundApp.windowClass}
+                        </button>
+                    ) : null}
+                    <button type="button" onClick={() => void onSaveAppRules(appRules)}>
+                        Save rules
+                    </button>
+                </div>
+            </>
+        )
+    }
+
+    async function onSaveSpokenPunctuation(settings: SpokenPunctuationSettings) {
+        try {
+            setErrorMessage('')
+            if (!hasTauriInvoke()) {
+                throw new Error('Tauri runtime unavailable. Open this UI from the Air Keys tray app.')
+            }
+            await invoke('set_spoken_punctuation', { settings })
+            setSpokenPunctuation(await invoke<SpokenPunctuationSettings>('get_spoken_punctuation'))
+        } catch (error) {
+            setErrorMessage(`Air Keys could not save the spoken commands: ${parseInvokeError(error)}`)
+        }
+    }
+
+    async function onSaveCleanupPipeline(stages: CleanupStage[]) {
+        try {
+            setErrorMessage('')
+            if (!hasTauriInvoke()) {
+                throw new Error('Tauri runtime unavailable. Open this UI from the Air Keys tray app.')
+            }
+            await invoke('set_cleanup_pipeline', { stages })
+            await Promise.all([refreshCleanupPipeline(), refreshProcessingEnabled()])
+        } catch (error) {
+            setErrorMessage(`Air Keys could not save the cleanup pipeline: ${parseInvokeError(error)}`)
+        }
+    }
+
+    function renderCleanupPipeline() {
+        if (cleanupPipeline.length === 0) {
+            return null
+        }
+        const updateStage = (index: number, changes: Partial<CleanupStage>) => {
+            setCleanupPipeline(
+                cleanupPipeline.map((stage, stageIndex) => (stageIndex === index ? { ...stage, ...changes } : stage)),
+            )
+        }
+        const moveStage = (index: number, offset: number) => {
+            const next = [...cleanupPipeline]
+            const [stage] = next.splice(index, 1)
+            next.splice(index + offset, 0, stage)
+            void onSaveCleanupPipeline(next)
+        }
+        return (
+            <>
+                <p className="settings-muted">
+                    Cleanup stages run top to bottom, each on the previous stage's output. A stage that fails or runs
+                    past its timeout is either skipped or stops the dictation without pasting anything.
+                </p>
+                {cleanupPipeline.map((stage, index) => (
+                    <div className="settings-form settings-inline-form" key={stage.kind}>
+                        <label className="settings-checkbox">
+                            <input
+                                type="checkbox"
+                                checked={stage.enabled}
+                                onChange={(event) =>
+                                    void onSaveCleanupPipeline(
+                                        cleanupPipeline.map((other, otherIndex) =>
+                                            otherIndex === index ? { ...other, enabled: event.target.checked } : other,
+                                        ),
+                                    )
+                                }
+                            />
+                            {STAGE_LABELS[stage.kind]}
+                        </label>
+                        <input
+                            aria-label="Timeout in milliseconds"
+                            type="number"
+                            min={100}
+                            max={120000}
+                            step={100}
+                            value={stage.timeoutMs}
+                            onChange={(event) => updateStage(index, { timeoutMs: Number(event.target.value) })}
+                        />
+                        <select
+                            aria-label="On failure"
+                            value={stage.onFailure}
+                            onChange={(event) => updateStage(index, { onFailure: event.target.value as FailurePolicy })}
+                        >
+                            <option value="skip">Skip on failure</option>
+                            <option value="abort">Abort on failure</option>
+                        </select>
+                        <button type="button" disabled={index === 0} onClick={() => moveStage(index, -1)}>
+                            Up
+                        </button>
+                        <button
+                            type="button"
+                            disabled={index === cleanupPipeline.length - 1}
+                            onClick={() => moveStage(index, 1)}
+                        >
+                            Down
+                        </button>
+                    </div>
+                ))}
+                <div className="settings-actions">
+                    <button type="button" onClick={() => void onSaveCleanupPipeline(cleanupPipeline)}>
+                        Save pipeline
+                    </button>
+                </div>
+            </>
+        )
+    }
+
+    function renderSpokenPunctuation() {
+        if (!spokenPunctuation) {
+            return null
+        }
+        const commands = spokenPunctuation.grammars[grammarLanguage] ?? []
+        const updateCommands = (next: SpokenCommand[]) => {
+            setSpokenPunctuation({
+                ...spokenPunctuation,
+                grammars: { ...spokenPunctuation.grammars, [grammarLanguage]: next },
+            })
+        }
+        const updateCommand = (index: number, changes: Partial<SpokenCommand>) => {
+            updateCommands(
+                commands.map((command, commandIndex) => (commandIndex === index ? { ...command, ...changes } : command)),
+            )
+        }
+        const grammarLanguages = languageOptions.filter(
+            (option) => option.value !== 'auto' && option.value !== 'multi',
+        )
+        const spokenStage = cleanupPipeline.find((stage) => stage.kind === 'spoken_punctuation')
+        return (
+            <>
+                <p className="settings-muted">
+                    Say "comma", "new line", "open quote" or "bullet point" and Air Keys inserts them on this device, even
+                    with AI cleanup off. Put AI cleanup after this stage and it may still reflow lines and bullets.
+                    Phrases are replaced wherever you say them, so words like "period" or "colon" become punctuation
+                    too; remove a command below if you need it as a word.
+                </p>
+                {!spokenStage?.enabled ? null : (
+                    <>
+                        <div className="settings-form settings-inline-form">
+                            <label htmlFor="grammarLanguage">Commands for</label>
+                            <select
+                                id="grammarLanguage"
+                                value={grammarLanguage}
+                                onChange={(event) => setGrammarLanguage(event.target.value)}
+                            >
+                                {grammarLanguages.map((option) => (
+                                    <option key={option.value} value={option.value}>
+                                        {option.label}
+                                    </option>
+                                ))}
+                            </select>
+                        </div>
+                        {commands.map((command, index) => (
+                            <div className="settings-form settings-inline-form" key={index}>
+                                <input
+                                    aria-label="Spoken phrase"
+                                    value={command.phrase}
+                                    onChange={(event) => updateCommand(index, { phrase: event.target.value })}
+                                />
+                                <input
+                                    aria-label="Inserted text"
+                                    spellCheck={false}
+                                    value={showCommandText(command.text)}
+                                    onChange={(event) => updateCommand(index, { text: parseCommandText(event.target.value) })}
+                                />
+                                <select
+                                    aria-label="Spacing"
+                                    value={command.spacing}
+                                    onChange={(event) => updateCommand(index, { spacing: event.target.value as Spacing })}
+                                >
+                                    {Object.entries(SPACING_LABELS).map(([value, label]) => (
+                                        <option key={value} value={value}>
+                                            {label}
+                                        </option>
+                                    ))}
+                                </select>
+                                <label className="settings-checkbox">
+                                    <input
+                                        type="checkbox"
+                                        checked={command.capitalizeNext}
+                                        onChange={(event) => updateCommand(index, { capitalizeNext: event.target.checked })}
+                                    />
+                                    Capitalise next
+                                </label>
+                                <button
+                                    type="button"
+                                    onClick={() => updateCommands(commands.filter((_, commandIndex) => commandIndex !== index))}
+                                >
+                                    Remove
+                                </button>
+                            </div>
+                        ))}
+                        <div className="settings-actions">
+                            <button
+                                type="button"
+                                onClick={() =>
+                                    updateCommands([
+                                        ...commands,
+                                        { phrase: '', text: '', spacing: 'word', capitalizeNext: false },
+                                    ])
+                                }
+                            >
+                                Add command
+                            </button>
+                            <button type="button" onClick={() => void onSaveSpokenPunctuation(spokenPunctuation)}>
+                                Save commands
+                            </button>
+                        </div>
+                    </>
+                )}
+            </>
+        )
+    }
+
+    async function onSaveReplacements(next: Replacement[]) {
+        try {
+            setErrorMessage('')
+            if (!hasTauriInvoke()) {
+                throw new Error('Tauri runtime unavailable. Open this UI from the Air Keys tray app.')
+            }
+            await invoke('set_replacements', { replacements: next })
+            await refreshReplacements()
+        } catch (error) {
+            setErrorMessage(`Air Keys could not save the replacements: ${parseInvokeError(error)}`)
+        }
+    }
+
+    async function onExportReplacements(format: ExchangeFormat) {
+        try {
+            setErrorMessage('')
+            if (!hasTauriInvoke()) {
+                throw new Error('Tauri runtime unavailable. Open this UI from the Air Keys tray app.')
+            }
+            const content = await invoke<string>('export_replacements', { format })
+            const type = format === 'csv' ? 'text/csv' : 'application/json'
+            const url = URL.createObjectURL(new Blob([content], { type }))
+            const link = document.createElement('a')
+            link.href = url
+            link.download = `air-keys-replacements.${format}`
+            link.click()
+            URL.revokeObjectURL(url)
+        } catch (error) {
+            setErrorMessage(`Air Keys could not export the replacements: ${parseInvokeError(error)}`)
+        }
+    }
+
+    async function onImportReplacements(file: File) {
+        try {
+            setErrorMessage('')
+            if (!hasTauriInvoke()) {
+                throw new Error('Tauri runtime unavailable. Open this UI from the Air Keys tray app.')
+            }
+            const format: ExchangeFormat = file.name.toLowerCase().endsWith('.json') ? 'json' : 'csv'
+            const content = await file.text()
+            setReplacements(await invoke<Replacement[]>('import_replacements', { format, content }))
+        } catch (error) {
+            setErrorMessage(`Air Keys could not import the replacements: ${parseInvokeError(error)}`)
+        }
+    }
+
+    function renderReplacements() {
+        const updateReplacement = (index: number, changes: Partial<Replacement>) => {
+            setReplacements(
+                replacements.map((replacement, replacementIndex) =>
+                    replacementIndex === index ? { ...replacement, ...changes } : replacement,
+                ),
+            )
+        }
+        return (
+            <>
+                <p className="settings-muted">
+                    Replacements fix words your transcription provider keeps getting wrong, such as "get hub" for
+                    GitHub. They run on this device, in table order, whatever providers you use. Regex entries can use
+                    $1 for captured groups.
+                </p>
+                {replacements.map((replacement, index) => (
+                    <div className="settings-form settings-inline-form" key={index}>
+                        <input
+                            aria-label="Find"
+                            spellCheck={false}
+                            value={replacement.find}
+                            placeholder="get hub"
+                            onChange={(event) => updateReplacement(index, { find: event.target.value })}
+                        />
+                        <input
+                            aria-label="Replace with"
+                            spellCheck={false}
+                            value={replacement.replace}
+                            placeholder="GitHub"
+                            onChange={(event) => updateReplacement(index, { replace: event.target.value })}
+                        />
+                        <select
+                            aria-label="Match"
+                            value={replacement.kind}
+                            onChange={(event) => updateReplacement(index, { kind: event.target.value as MatchKind })}
+                        >
+                            <option value="literal">Text</option>
+                            <option value="regex">Regex</option>
+                        </select>
+                        <label className="settings-checkbox">
+                            <input
+                                type="checkbox"
+                                checked={replacement.wholeWord}
+                                onChange={(event) => updateReplacement(index, { wholeWord: event.target.checked })}
+                            />
+                            Whole words
+                        </label>
+                        <label className="settings-checkbox">
+                            <input
+                                type="checkbox"
+                                checked={replacement.caseSensitive}
+                                onChange={(event) => updateReplacement(index, { caseSensitive: event.target.checked })}
+                            />
+                            Match case
+                        </label>
+                        <label className="settings-checkbox">
+                            <input
+                                type="checkbox"
+                                checked={replacement.preserveCase}
+                                onChange={(event) => updateReplacement(index, { preserveCase: event.target.checked })}
+                            />
+                            Keep capitals
+                        </label>
+                        <button
+                            type="button"
+                            onClick={() =>
+                                setReplacements(replacements.filter((_, replacementIndex) => replacementIndex !== index))
+                            }
+                        >
+                            Remove
+                        </button>
+                    </div>
+                ))}
+                <div className="settings-actions">
+                    <button
+                        type="button"
+                        onClick={() =>
+                            setReplacements([
+                                ...replacements,
+                                {
+                                    find: '',
+                                    replace: '',
+                                    kind: 'literal',
+                                    wholeWord: true,
+                                    caseSensitive: false,
+                                    preserveCase: false,
+                                },
+                            ])
+                        }
+                    >
+                        Add replacement
+                    </button>
+                    <button type="button" onClick={() => void onSaveReplacements(replacements)}>
+                        Save replacements
+                    </button>
+                    <button type="button" onClick={() => void onExportReplacements('csv')}>
+                        Export CSV
+                    </button>
+                    <button type="button" onClick={() => void onExportReplacements('json')}>
+                        Export JSON
+                    </button>
+                    <label className="settings-checkbox">
+                        Import
+                        <input
+                            type="file"
+                            accept=".csv,.json"
+                            onChange={(event) => {
+                                const file = event.target.files?.[0]
+                                event.target.value = ''
+                                if (file) {
+                                    void onImportReplacements(file)
+                                }
+                            }}
+                        />
+                    </label>
+                </div>
+            </>
+        )
+    }
+
+    async function onClearHistory() {
+        try {
+            setErrorMessage('')
+            if (!hasTauriInvoke()) {
+                throw new Error('Tauri runtime unavailable. Open this UI from the Air Keys tray app.')
+            }
+            await invoke('clear_history')
+            setHistory([])
+        } catch (error) {
+            setErrorMessage(`Air Keys could not clear history: ${parseInvokeError(error)}`)
+        }
+    }
+
+    function renderSecondOpinion() {
+        const candidates = providers.filter(
+            (provider) =>
+                provider.kind === 'transcription' && provider.id !== activeProviders?.transcription,
+        )
+        if (candidates.length === 0) {
+            return null
+        }
+        return (
+            <div className="settings-form settings-inline-form">
+                <label htmlFor="secondOpinion">Second opinion</label>
+                <select
+                    id="secondOpinion"
+                    value={secondOpinion.provider ?? ''}
+                    disabled={isSavingProviders}
+                    onChange={(event) => {
+                        void onSecondOpinionChange({
+                            ...secondOpinion,
+                            provider: event.target.value || null,
+                        })
+                    }}
+                >
+                    <option value="">Off</option>
+                    {candidates.map((provider) => (
+                        <option key={provider.id} value={provider.id}>
+                            {provider.name}
+                        </option>
+                    ))}
+                </select>
+                {secondOpinion.provider ? (
+                    <>
+                        <label htmlFor="secondOpinionStrategy">Combine results by</label>
+                        <select
+                            id="secondOpinionStrategy"
+                            value={secondOpinion.strategy}
+                            disabled={isSavingProviders}
+                            onChange={(event) => {
+                                void onSecondOpinionChange({
+                                    ...secondOpinion,
+                                    strategy: event.target.value as MergeStrategy,
+                                })
+                            }}
+                        >
+                            <option value="higher_confidence">Higher average confidence</option>
+                            <option value="word_by_word">Word-by-word vote</option>
+                        </select>
+                    </>
+                ) : null}
+                <p className="settings-muted">
+                    Both engines transcribe every dictation and are billed for it. Where they
+                    disagree, the dictation is saved to history below.
+                </p>
+            </div>
+        )
+    }
+
+    async function onBlocklistBlur() {
+        const phrases = blocklistText
+            .split('\n')
+            .map((phrase) => phrase.trim())
+            .filter((phrase) => phrase.length > 0)
+        try {
+            setErrorMessage('')
+            if (!hasTauriInvoke()) {
+                throw new Error('Tauri runtime unavailable. Open this UI from the Air Keys tray app.')
+            }
+            await invoke('set_hallucination_blocklist', { phrases })
+            setBlocklistText(phrases.join('\n'))
+        } catch (error) {
+            setErrorMessage(`Air Keys could not update ignored phrases: ${parseInvokeError(error)}`)
+        }
+    }
+
+    async function onHttpPolicyChange(key: keyof HttpPolicy, value: number) {
+        if (!httpPolicy || !Number.isFinite(value) || httpPolicy[key] === value) {
+            return
+        }
+        const policy = { ...httpPolicy, [key]: value }
+        try {
+            setIsSavingHttpPolicy(true)
+            setErrorMessage('')
+            if (!hasTauriInvoke()) {
+                throw new Error('Tauri runtime unavailable. Open this UI from the Air Keys tray app.')
+            }
+            await invoke('set_http_policy', { policy })
+            setHttpPolicy(policy)
+        } catch (error) {
+            setErrorMessage(`Air Keys could not update network settings: ${parseInvokeError(error)}`)
+        } finally {
+            setIsSavingHttpPolicy(false)
+        }
+    }
+
+    async function onProxySettingChange<K extends keyof ProxySettings>(key: K, value: ProxySettings[K]) {
+        if (!proxySettings || proxySettings[key] === value) {
+            return
+        }
+        const settings = { ...proxySettings, [key]: value }
+        try {
+            setIsSavingHttpPolicy(true)
+            setErrorMessage('')
+            if (!hasTauriInvoke()) {
+                throw new Error('Tauri runtime unavailable. Open this UI from the Air Keys tray app.')
+            }
+            await invoke('set_proxy_settings', { settings })
+            setProxySettings(settings)
+        } catch (error) {
+            setErrorMessage(`Air Keys could not update proxy settings: ${parseInvokeError(error)}`)
+        } finally {
+            setIsSavingHttpPolicy(false)
+        }
+    }
+
+    async function onProxyPasswordChange(password: string | null) {
+        try {
+            setIsSavingHttpPolicy(true)
+            setErrorMessage('')
+            if (!hasTauriInvoke()) {
+                throw new Error('Tauri runtime unavailable. Open this UI from the Air Keys tray app.')
+            }
+            if (password === null) {
+                await invoke('clear_proxy_password')
+            } else {
+                await invoke('save_proxy_password', { password })
+            }
+            setHasProxyPassword(password !== null)
+        } catch (error) {
+            setErrorMessage(`Air Keys could not update the proxy password: ${parseInvokeError(error)}`)
+        } finally {
+            setIsSavingHttpPolicy(false)
+        }
+    }
+
+    async function onImportCertificates(file: File | undefined) {
+        if (!file || !proxySettings) {
+            return
+        }
+        const imported = (await file.text()).trim()
+        const existing = proxySettings.caCertificatesPem?.trim()
+        await onProxySettingChange('caCertificatesPem', existing ? `${existing}\n${imported}` : imported)
+    }
+
+    function renderProxySettings() {
+        if (!proxySettings) {
+            return null
+        }
+        return (
+            <>
+                <div className="settings-form settings-inline-form">
+                    <div className="settings-form">
+                        <label htmlFor="proxyUrl">Proxy URL</label>
+                        <input
+                            id="proxyUrl"
+                            type="url"
+                            placeholder="http://proxy.example.com:8080"
+                            spellCheck={false}
+                            defaultValue={proxySettings.url ?? ''}
+                            disabled={isSavingHttpPolicy}
+                            onBlur={(event) => {
+                                void onProxySettingChange('url', event.target.value.trim() || null)
+                            }}
+                        />
+                    </div>
+                    <div className="settings-form">
+                        <label htmlFor="proxyUsername">Proxy username</label>
+                        <input
+                            id="proxyUsername"
+                            type="text"
+                            autoComplete="off"
+                            spellCheck={false}
+                            defaultValue={proxySettings.username ?? ''}
+                            disabled={isSavingHttpPolicy}
+                            onBlur={(event) => {
+                                void onProxySettingChange('username', event.target.value.trim() || null)
+                            }}
+                        />
+                    </div>
+                    <div className="settings-form">
+                        <label htmlFor="proxyPassword">Proxy password</label>
+                        <input
+                            id="proxyPassword"
+                            type="password"
+                            autoComplete="off"
+                            placeholder={hasProxyPassword ? API_KEY_MASK : ''}
+                            disabled={isSavingHttpPolicy}
+                            onBlur={(event) => {
+                                if (event.target.value) {
+                                    void onProxyPasswordChange(event.target.value)
+                                    event.target.value = ''
+                                }
+                            }}
+                        />
+                        {hasProxyPassword ? (
+                            <button
+                                type="button"
+                                disabled={isSavingHttpPolicy}
+                                onClick={() => void onProxyPasswordChange(null)}
+                            >
+                                Clear password
+                            </button>
+                        ) : null}
+                    </div>
+                </div>
+                <div className="settings-form">
+                    <label htmlFor="noProxy">Bypass proxy for</label>
+                    <input
+                        id="noProxy"
+                        type="text"
+                        placeholder="localhost, 127.0.0.1, .internal.example.com"
+                        spellCheck={false}
+                        defaultValue={proxySettings.noProxy}
+                        disabled={isSavingHttpPolicy}
+                        onBlur={(event) => {
+                            void onProxySettingChange('noProxy', event.target.value.trim())
+                        }}
+                    />
+                </div>
+                <div className="settings-form settings-inline-form">
+                    <label htmlFor="caCertificates">Additional trusted certificates (PEM)</label>
+                    <textarea
+                        id="caCertificates"
+                        key={proxySettings.caCertificatesPem ?? ''}
+                        rows={4}
+                        spellCheck={false}
+                        placeholder="-----BEGIN CERTIFICATE-----"
+                        defaultValue={proxySettings.caCertificatesPem ?? ''}
+                        disabled={isSavingHttpPolicy}
+                        onBlur={(event) => {
+                            void onProxySettingChange('caCertificatesPem', event.target.value.trim() || null)
+                        }}
+                    />
+                    <input
+                        type="file"
+                        accept=".pem,.crt,.cer"
+                        aria-label="Import certificates"
+                        disabled={isSavingHttpPolicy}
+                        onChange={(event) => {
+                            void onImportCertificates(event.target.files?.[0])
+                            event.target.value = ''
+                        }}
+                    />
+                    <p className="settings-muted">
+                        Trusted in addition to the system certificates, e.g. the root certificate of a
+                        TLS-inspecting proxy.
+                    </p>
+                </div>
+            </>
+        )
+    }
+
+    return (
+        <main className="settings-shell">
+            <h1>Air Keys settings</h1>
+            <p className="settings-subtitle">Press Alt twice to start/stop dictation. <br />Hold Alt to cancel in progress recording.</p>
+            <br />
+            <p className="settings-help">
+                Air Keys runs from the system tray. Configure your transcription and post-processing
+                keys here.
+            </p>
+
+            <section className="settings-section">
+                <form className="settings-form" onSubmit={onSaveDeepgram}>
+                    <label htmlFor="deepgramKey">Deepgram API key</label>
+                    <input
+                        id="deepgramKey"
+                        type="password"
+                        autoComplete="off"
+                        spellCheck={false}
+                        value={deepgramApiKey}
+                        readOnly={hasDeepgramKey}
+                        onChange={(event) => {
+                            setDeepgramApiKey(event.target.value)
+                            if (deepgramSaveState !== 'idle') {
+                                setDeepgramSaveState('idle')
+                            }
+                        }}
+                        placeholder="dg_live_..."
+                    />
+                    <div className="settings-actions">
+                        <button
+                            type="submit"
+                            disabled={
+                                deepgramSaveState === 'validating' ||
+                                deepgramSaveState === 'saving' ||
+                                hasDeepgramKey
+                            }
+                        >
+                            {deepgramSaveButtonText}
+                        </button>
+                        <button type="button" onClick={onClearDeepgram}>
+                            Clear key
+                        </button>
+                    </div>
+                </form>
+                <p className="settings-status">
+                    Stored key:{' '}
+                    <strong>
+                        {isCheckingDeepgramStatus ? 'checking...' : hasDeepgramKey ? 'present' : 'not set'}
+                    </strong>
+                </p>
+            </section>
+
+            <section className="settings-section">
+                <h2>Engines</h2>
+                <p className="settings-muted">
+                    Changes apply to the next dictation; no restart needed.
+                </p>
+                {renderProviderPicker('transcription', 'Transcription provider')}
+                <div className="settings-form settings-inline-form">
+                    <label htmlFor="language">Language</label>
+                    <select
+                        id="language"
+                        value={language}
+                        disabled={isSavingProviders}
+                        onChange={(event) => void onLanguageChange(event.target.value)}
+                    >
+                        {languageOptions.map((option) => (
+                            <option key={option.value} value={option.value}>
+                                {option.label}
+                            </option>
+                        ))}
+                    </select>
+                    <p className="settings-muted">
+                        Multilingual handles switching languages mid-sentence. It can also be
+                        changed from the tray menu.
+                    </p>
+                </div>
+                <label className="settings-checkbox settings-checkbox-spaced" htmlFor="reviewEnabled">
+                    <input
+                        id="reviewEnabled"
+                        type="checkbox"
+                        checked={reviewEnabled}
+                        disabled={isSavingProviders}
+                        onChange={(event) => {
+                            void onReviewEnabledChange(event.target.checked)
+                        }}
+                    />
+                    Review uncertain words before pasting
+                </label>
+                <p className="settings-muted">
+                    When a transcript contains low-confidence words, the overlay shows it first.
+                    Press Enter to paste or Esc to discard.
+                </p>
+                {renderFallbackList()}
+                {renderSecondOpinion()}
+                <div className="settings-form settings-inline-form">
+                    <label htmlFor="hallucinationBlocklist">Ignored phrases</label>
+                    <textarea
+                        id="hallucinationBlocklist"
+                        rows={4}
+                        spellCheck={false}
+                        value={blocklistText}
+                        onChange={(event) => setBlocklistText(event.target.value)}
+                        onBlur={() => void onBlocklistBlur()}
+                    />
+                    <p className="settings-muted">
+                        One per line. When a dictation is only one of these phrases and the engine
+                        is not confident, it is treated as noise and nothing is pasted. Near-silent
+                        recordings are always discarded.
+                    </p>
+                </div>
+                {renderProviderPicker('cleanup', 'Post-processing provider')}
+            </section>
+
+            {history.length > 0 ? (
+                <section className="settings-section">
+                    <h2>History</h2>
+                    <p className="settings-muted">
+                        Dictations where the second opinion disagreed. Stored only on this device.
+                    </p>
+                    {history.map((entry) => (
+                        <div className="settings-history-entry" key={entry.timestamp}>
+                            <p className="settings-status">
+                                {new Date(entry.timestamp).toLocaleString()} · {entry.provider}
+                            </p>
+                            <p>{entry.text}</p>
+                            {entry.secondOpinion ? (
+                                <ul className="settings-muted">
+                                    {entry.secondOpinion.disagreements.map((disagreement, index) => (
+                                        <li key={index}>
+                                            {entry.secondOpinion?.primaryProvider}: “
+                                            {disagreement.primary ?? '—'}” ·{' '}
+                                            {entry.secondOpinion?.secondaryProvider}: “
+                                            {disagreement.secondary ?? '—'}” → kept “
+                                            {disagreement.chosen ?? '—'}”
+                                        </li>
+                                    ))}
+                                </ul>
+                            ) : null}
+                        </div>
+                    ))}
+                    <div className="settings-actions">
+                        <button type="button" onClick={() => void onClearHistory()}>
+                            Clear history
+                        </button>
+                    </div>
+                </section>
+            ) : null}
+
+            <section className="settings-section">
+                <h2>Usage</h2>
+                <p className="settings-muted">
+                    Audio sent to transcription providers and tokens used for post-processing,
+                    recorded on this device. Costs are estimates from list prices; servers on this
+                    computer or network are free, and unlisted hosts or models are counted at a high
+                    rate. Your provider bill is authoritative.
+                </p>
+                {usageSummary?.monthly.map((period) => (
+                    <div className="settings-history-entry" key={period.period}>
+                        <p className="settings-status">
+                            {period.period} · ~${period.estimatedCostUsd.toFixed(2)}
+                        </p>
+                        <ul className="settings-muted">
+                            {period.providers.map((usage) => (
+                                <li key={usage.provider}>{describeUsage(usage)}</li>
+                            ))}
+                        </ul>
+                    </div>
+                ))}
+                {usageSummary && usageSummary.daily.length > 0 ? (
+                    <ul className="settings-muted">
+                        {usageSummary.daily.slice(0, USAGE_DAYS_SHOWN).map((period) => (
+                            <li key={period.period}>
+                                {period.period}: ~${period.estimatedCostUsd.toFixed(2)}
+                            </li>
+                        ))}
+                    </ul>
+                ) : null}
+                <div className="settings-form settings-inline-form">
+                    <div className="settings-form">
+                        <label htmlFor="monthlyLimit">Monthly spending limit (USD)</label>
+                        <input
+                            id="monthlyLimit"
+                            key={String(usageQuota.monthlyLimitUsd)}
+                            type="number"
+                            min={0}
+                            step={0.5}
+                            placeholder="No limit"
+                            defaultValue={usageQuota.monthlyLimitUsd ?? ''}
+                            onBlur={(event) => {
+                                const value = event.target.value.trim()
+                                const monthlyLimitUsd = value ? Number(value) : null
+                                if (monthlyLimitUsd !== usageQuota.monthlyLimitUsd) {
+                                    void onUsageQuotaChange({ ...usageQuota, monthlyLimitUsd })
+                                }
+                            }}
+                        />
+                    </div>
+                    <div className="settings-form">
+                        <label htmlFor="quotaMode">When reached</label>
+                        <select
+                            id="quotaMode"
+                            value={usageQuota.mode}
+                            onChange={(event) => {
+                                void onUsageQuotaChange({
+                                    ...usageQuota,
+                                    mode: event.target.value as UsageQuota['mode'],
+                                })
+                            }}
+                        >
+                            <option value="soft">Warn and keep dictating</option>
+                            <option value="hard">Stop paid requests</option>
+                        </select>
+                    </div>
+                </div>
+            </section>
+
+            <section className="settings-section">
+                <h2>App rules</h2>
+                <p className="settings-muted">
+                    Air Keys checks which app is focused when you start dictating. The first matching rule picks
+                    the cleanup profile, language and how text is inserted; anything left unset uses the global
+                    settings.
+                </p>
+                {renderAppRules()}
+            </section>
+
+            <section className="settings-section">
+                <h2>General</h2>
+                <label className="settings-checkbox" htmlFor="launchOnStartup">
                     <input
                         id="launchOnStartup"
                         type="checkbox"
@@ -406,7 +2596,7 @@ export function SettingsPage() {
                 <p className="settings-muted">
                     {isCheckingProcessingEnabled
                         ? 'Checking post-processing setting...'
-                        : 'When enabled, Air Keys uses Gemini to remove fillers and smooth phrasing.'}
+                        : 'When enabled, Air Keys uses the post-processing provider to remove fillers and smooth phrasing.'}
                 </p>
                 {processingEnabled ? (
                     <>
@@ -449,8 +2639,103 @@ export function SettingsPage() {
                                 {isCheckingGeminiStatus ? 'checking...' : hasGeminiKey ? 'present' : 'not set'}
                             </strong>
                         </p>
+                        {activeProviders?.cleanup === 'openai_compatible' ? (
+                            <form className="settings-form settings-inline-form" onSubmit={onSaveOpenAiCompatible}>
+                                <label htmlFor="openAiCompatibleKey">OpenAI-compatible API key (optional)</label>
+                                <input
+                                    id="openAiCompatibleKey"
+                                    type="password"
+                                    autoComplete="off"
+                                    spellCheck={false}
+                                    value={openAiCompatibleApiKey}
+                                    readOnly={hasOpenAiCompatibleKey}
+                                    onChange={(event) => setOpenAiCompatibleApiKey(event.target.value)}
+                                    placeholder="Not needed for Ollama"
+                                />
+                                <div className="settings-actions">
+                                    <button type="submit" disabled={hasOpenAiCompatibleKey || !openAiCompatibleApiKey.trim()}>
+                                        Save key
+                                    </button>
+                                    <button type="button" onClick={onClearOpenAiCompatible}>
+                                        Clear key
+                                    </button>
+                                </div>
+                            </form>
+                        ) : null}
+                        {renderProfiles()}
                     </>
                 ) : null}
+                <label className="settings-checkbox settings-checkbox-spaced" htmlFor="contextCaptureEnabled">
+                    <input
+                        id="contextCaptureEnabled"
+                        type="checkbox"
+                        checked={contextCaptureEnabled}
+                        disabled={isSavingProviders}
+                        onChange={(event) => {
+                            void onContextCaptureEnabledChange(event.target.checked)
+                        }}
+                    />
+                    Use the text before the cursor as context
+                </label>
+                <p className="settings-muted">
+                    Cleanup matches the capitalization, spacing and terms of what you already wrote, and
+                    continues an unfinished sentence. The text is read through accessibility where the
+                    app allows it. App rules can also allow briefly selecting and copying the current
+                    line; leave that off for terminals, where Ctrl+C interrupts. It is only sent to the
+                    post-processing provider and never pasted.
+                </p>
+                <label className="settings-checkbox settings-checkbox-spaced" htmlFor="commandModeEnabled">
+                    <input
+                        id="commandModeEnabled"
+                        type="checkbox"
+                        checked={commandModeEnabled}
+                        disabled={isSavingProviders}
+                        onChange={(event) => {
+                            void onCommandModeEnabledChange(event.target.checked)
+                        }}
+                    />
+                    Double-tap Right Alt to rewrite the selected text
+                </label>
+                <p className="settings-muted">
+                    Select text, double-tap Right Alt and say what to change; the post-processing provider
+                    rewrites the selection. Off by default: on keyboard layouts where Right Alt is AltGr,
+                    leave it off to keep dictating with either Alt key.
+                </p>
+                {renderCleanupPipeline()}
+                {renderSpokenPunctuation()}
+                {renderReplacements()}
+            </section>
+
+            <section className="settings-section">
+                <h2>Network</h2>
+                <p className="settings-muted">
+                    Applies to every request Air Keys makes to transcription and post-processing
+                    providers. Failed connections, timeouts and rate limits are retried with backoff.
+                </p>
+                {httpPolicy ? (
+                    <div className="settings-form settings-inline-form">
+                        {HTTP_POLICY_FIELDS.map((field) => (
+                            <div className="settings-form" key={field.key}>
+                                <label htmlFor={field.key}>{field.label}</label>
+                                <input
+                                    id={field.key}
+                                    type="number"
+                                    min={0}
+                                    step={field.scale === 1 ? 1 : 0.5}
+                                    defaultValue={httpPolicy[field.key] / field.scale}
+                                    disabled={isSavingHttpPolicy}
+                                    onBlur={(event) => {
+                                        void onHttpPolicyChange(
+                                            field.key,
+                                            Math.round(Number(event.target.value) * field.scale),
+                                        )
+                                    }}
+                                />
+                            </div>
+                        ))}
+                    </div>
+                ) : null}
+                {renderProxySettings()}
             </section>
 
             {errorMessage ? <p className="settings-error">{errorMessage}</p> : null}